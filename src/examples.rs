@@ -1,12 +1,15 @@
 #![warn(missing_docs)]
+use std::error::Error;
 use std::ops::Div;
-use std::{error::Error, f32::consts::TAU};
 
 use itertools_num::linspace;
 use mentat::MonotonicCubicSpline;
 use statrs::consts;
 
-use visualize::{design::*, file_handler::*, functions::*, plot::*};
+use visualize::{
+    annotations::*, bars::*, candles::*, colormap::*, design::*, distributions::*, fan::*,
+    figure::*, file_handler::*, functions::*, histogram::*, plot::*,
+};
 
 #[allow(unused)]
 /// Plot of different types of approximations to the Gaussian PDF
@@ -18,62 +21,60 @@ pub fn compare_approximation_types(display: Display) {
     let number_of_points = 1000;
     let t = linspace(t_start, t_end, number_of_points).collect::<Vec<f64>>();
     // Build the polynomial approximation
-    let polynomial_approximation_design = CurveDesign {
-        color: Color::Purple,
-        color_slot: MAIN_COLOR_SLOT,
-        style: Style::Lines(LineEmphasis::Light),
-    };
-    let polynomial_approximation = Curve {
-        x_coordinates: t.clone(),
-        y_coordinates: t.iter().map(|x| 1.0 - x * x).collect(),
-        design: polynomial_approximation_design,
-        name: Some(String::from("1-x^2")),
-    };
+    let polynomial_approximation_design = CurveDesign::new(
+        Color::Purple,
+        MAIN_COLOR_SLOT,
+        Style::Lines(LineEmphasis::Light),
+    );
+    let polynomial_approximation = Curve::new(
+        t.clone(),
+        t.iter().map(|x| 1.0 - x * x).collect(),
+        polynomial_approximation_design,
+    )
+    .name("1-x^2");
     // Build the rational approximation
-    let rational_approximation_design = CurveDesign {
-        color: Color::Blue,
-        color_slot: MAIN_COLOR_SLOT,
-        style: Style::Lines(LineEmphasis::Light),
-    };
-    let rational_approximation = Curve {
-        x_coordinates: t.clone(),
-        y_coordinates: t.iter().map(|x| 1.0 / (1.0 + x * x)).collect(),
-        design: rational_approximation_design,
-        name: Some(String::from("(1-x^2)^{-1}")),
-    };
+    let rational_approximation_design = CurveDesign::new(
+        Color::Blue,
+        MAIN_COLOR_SLOT,
+        Style::Lines(LineEmphasis::Light),
+    );
+    let rational_approximation = Curve::new(
+        t.clone(),
+        t.iter().map(|x| 1.0 / (1.0 + x * x)).collect(),
+        rational_approximation_design,
+    )
+    .name("(1-x^2)^{-1}");
     // Build the Gaussian PDF
-    let gaussian_pdf_design = CurveDesign {
-        color: Color::Green,
-        color_slot: MAIN_COLOR_SLOT,
-        style: Style::Lines(LineEmphasis::Heavy),
-    };
-    let gaussian_pdf = Curve {
-        x_coordinates: t.clone(),
-        y_coordinates: standard_gaussian_pdf(t.iter().map(|x| 2.0_f64.sqrt() * x).collect())
+    let gaussian_pdf_design = CurveDesign::new(
+        Color::Green,
+        MAIN_COLOR_SLOT,
+        Style::Lines(LineEmphasis::Heavy),
+    );
+    let gaussian_pdf = Curve::new(
+        t.clone(),
+        standard_gaussian_pdf(t.iter().map(|x| 2.0_f64.sqrt() * x).collect())
             .iter()
             .map(|y| consts::SQRT_2PI * y)
             .collect(),
-        design: gaussian_pdf_design,
-        name: Some(String::from("\\exp\\left(-x^2\\right)")),
-    };
+        gaussian_pdf_design,
+    )
+    .name("\\exp\\left(-x^2\\right)");
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("x"),
         y_label: String::from("f(x)"),
         bounds: (vec![t_start, t_end], vec![-0.5, 1.5]),
+        ..Default::default()
     };
     //plot
-    transparent_plot(
-        Some(vec![
-            polynomial_approximation,
-            rational_approximation,
-            gaussian_pdf,
-        ]),
-        None,
-        axes,
-        title,
-        display, None
-    );
+    Figure::new()
+        .curve(polynomial_approximation)
+        .curve(rational_approximation)
+        .curve(gaussian_pdf)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 #[allow(unused)]
 /// Plots of polynomial approximations to the Gaussian PDF
@@ -94,48 +95,48 @@ pub fn polynomial_approximations(display: Display) {
             _ => 0.0,
         })
         .collect::<Vec<f64>>();
-    // Build the polynomial approximations
+    // Build the polynomial approximations, keeping green free for the Gaussian PDF
     let mut curves = vec![];
-    for degree in coefficient_range.step_by(2) {
-        let curve = Curve {
-            x_coordinates: t.clone(),
-            y_coordinates: polynomial_approx(
-                t.clone(),
-                coefficients[0..1 + degree as usize].to_vec(),
-            ),
-            design: CurveDesign {
-                color: Color::Purple,
-                color_slot: degree as usize,
-                style: Style::Lines(LineEmphasis::Light),
-            },
-            name: Some(format!("{} {}", "\\text{Degree }", degree)),
-        };
+    let colors = ColorCycle::of(vec![Color::Purple, Color::Blue]);
+    for (degree, (color, color_slot)) in coefficient_range.step_by(2).zip(colors) {
+        let curve = Curve::new(
+            t.clone(),
+            polynomial_approx(t.clone(), coefficients[0..1 + degree as usize].to_vec()),
+            CurveDesign::new(color, color_slot, Style::Lines(LineEmphasis::Light)),
+        )
+        .name(format!("{} {}", "\\text{Degree }", degree));
         curves.push(curve);
     }
     // Build the Gaussian PDF
-    let gaussian_pdf_design = CurveDesign {
-        color: Color::Green,
-        color_slot: MAIN_COLOR_SLOT,
-        style: Style::Lines(LineEmphasis::Heavy),
-    };
-    let gaussian_pdf = Curve {
-        x_coordinates: t.clone(),
-        y_coordinates: standard_gaussian_pdf(t.iter().map(|x| 2.0_f64.sqrt() * x).collect())
+    let gaussian_pdf_design = CurveDesign::new(
+        Color::Green,
+        MAIN_COLOR_SLOT,
+        Style::Lines(LineEmphasis::Heavy),
+    );
+    let gaussian_pdf = Curve::new(
+        t.clone(),
+        standard_gaussian_pdf(t.iter().map(|x| 2.0_f64.sqrt() * x).collect())
             .iter()
             .map(|y| consts::SQRT_2PI * y)
             .collect(),
-        design: gaussian_pdf_design,
-        name: Some(String::from("\\exp\\left(-x^2\\right)")),
-    };
+        gaussian_pdf_design,
+    )
+    .name("\\exp\\left(-x^2\\right)");
     curves.push(gaussian_pdf);
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("x"),
         y_label: String::from("f(x)"),
         bounds: (vec![t_start, t_end], vec![-0.5, 1.5]),
+        ..Default::default()
     };
     // plot
-    transparent_plot(Some(curves), None, axes, title, display, None);
+    Figure::new()
+        .curves(curves)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 #[allow(unused)]
 /// Plot RMM trading curve for multiple taus from a list of prices
@@ -152,19 +153,15 @@ pub fn rmm_trading_curve_multiple_taus(display: Display) {
     let prices = linspace(price_start, price_end, number_of_prices).collect::<Vec<f64>>();
     // Build the curves
     let mut curves = vec![];
-    for (index, tau) in taus.iter().enumerate() {
+    for (tau, (color, color_slot)) in taus.iter().zip(ColorCycle::new()) {
         let (reserves_x_tau, reserves_y_tau) =
             rmm_trading_curve(prices.clone(), strike, sigma, *tau, None);
-        let curve = Curve {
-            x_coordinates: reserves_x_tau,
-            y_coordinates: reserves_y_tau,
-            design: CurveDesign {
-                color: Color::Green,
-                color_slot: index,
-                style: Style::Lines(LineEmphasis::Light),
-            },
-            name: Some(format!("{} {}", "\\tau=", tau)),
-        };
+        let curve = Curve::new(
+            reserves_x_tau,
+            reserves_y_tau,
+            CurveDesign::new(color, color_slot, Style::Lines(LineEmphasis::Light)),
+        )
+        .name(format!("{} {}", "\\tau=", tau));
         curves.push(curve);
     }
     // Build the plot's axes
@@ -172,9 +169,19 @@ pub fn rmm_trading_curve_multiple_taus(display: Display) {
         x_label: String::from("R_x"),
         y_label: String::from("R_y"),
         bounds: (vec![0.0, 1.0], vec![0.0, 3.0]),
+        ..Default::default()
     };
-    // plot
-    transparent_plot(Some(curves), None, axes, title, display, None);
+    // plot, keeping the legend clear of the curves bending towards the origin
+    Figure::new()
+        .curves(curves)
+        .axes(axes)
+        .title(title)
+        .legend(LegendLayout {
+            position: LegendPosition::TopRight,
+            ..Default::default()
+        })
+        .display(display)
+        .plot();
 }
 #[allow(unused)]
 /// Plot RMM trading curve for multiple rescalings
@@ -204,16 +211,16 @@ pub fn rmm_trading_curve_rescaling(display: Display) {
     for scale_factor in scale_factors {
         let (x_scale, y_scale) =
             rmm_trading_curve(prices.clone(), strike, sigma, tau, Some(scale_factor));
-        let curve = Curve {
-            x_coordinates: x_scale,
-            y_coordinates: y_scale,
-            design: CurveDesign {
-                color: Color::Green,
-                color_slot: MAIN_COLOR_SLOT,
-                style: Style::Lines(LineEmphasis::Light),
-            },
-            name: Some(format!("{} {}", "\\text{Scale }", scale_factor)),
-        };
+        let curve = Curve::new(
+            x_scale,
+            y_scale,
+            CurveDesign::new(
+                Color::Green,
+                MAIN_COLOR_SLOT,
+                Style::Lines(LineEmphasis::Light),
+            ),
+        )
+        .name(format!("{} {}", "\\text{Scale }", scale_factor));
         curves.push(curve);
     }
     // Build the plot's axes
@@ -221,9 +228,15 @@ pub fn rmm_trading_curve_rescaling(display: Display) {
         x_label: String::from("R_x"),
         y_label: String::from("R_y"),
         bounds: (vec![0.0, 1.0], vec![0.0, 3.0]),
+        ..Default::default()
     };
     // plot
-    transparent_plot(Some(curves), None, axes, title, display, None);
+    Figure::new()
+        .curves(curves)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 
 /// Plot RMM liquidity distribution for multiple taus
@@ -252,19 +265,19 @@ pub fn rmm_liquidity_distribution(display: Display) {
             after_divide.push(y_val / prices[i]);
         }
 
-        let curve = Curve {
-            x_coordinates: prices.clone(),
-            y_coordinates: after_divide,
-            design: CurveDesign {
-                color: Color::Green,
-                color_slot: MAIN_COLOR_SLOT,
-                style: Style::Lines(LineEmphasis::Heavy),
-            },
-            name: Some(format!(
-                "{} {} {} {} {} {} {}",
-                "L = 1", "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
-            )),
-        };
+        let curve = Curve::new(
+            prices.clone(),
+            after_divide,
+            CurveDesign::new(
+                Color::Green,
+                MAIN_COLOR_SLOT,
+                Style::Lines(LineEmphasis::Heavy),
+            ),
+        )
+        .name(format!(
+            "{} {} {} {} {} {} {}",
+            "L = 1", "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
+        ));
         curves.push(curve);
     }
     // Build the plot's axes
@@ -272,9 +285,15 @@ pub fn rmm_liquidity_distribution(display: Display) {
         x_label: String::from("\\textrm{Price, }S"),
         y_label: String::from("\\textrm{Depth}"),
         bounds: (vec![price_start, price_end], vec![0.0, 1.5]),
+        ..Default::default()
     };
     // plot
-    transparent_plot(Some(curves), None, axes, title, display, None);
+    Figure::new()
+        .curves(curves)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 
 /// Plot RMM portfolio value for multiple taus
@@ -284,7 +303,7 @@ pub fn rmm_portfolio_value(display: Display) {
     // Define the relavant RMM-CC parameters with multiple taus
     let strike = 3_f64;
     let sigma = 0.5_f64;
-    let taus = vec![2.0_f64, 1.5_f64, 1.0_f64, 0.5_f64, 0_f64];
+    let taus = [2.0_f64, 1.5_f64, 1.0_f64, 0.5_f64, 0_f64];
     // Create a list of prices that we will compute the reserves from
     let price_start = 0.0_f64;
     let price_end = 10.0_f64;
@@ -307,43 +326,84 @@ pub fn rmm_portfolio_value(display: Display) {
             .map(|(&x, &y)| x * y)
             .collect::<Vec<f64>>();
         let temp2 = standard_gaussian_cdf(d_two(prices.clone(), strike, sigma, *tau));
-        let curve = Curve {
-            x_coordinates: prices.clone(),
-            y_coordinates: temp1
+        let curve = Curve::new(
+            prices.clone(),
+            temp1
                 .iter()
                 .zip(temp2.iter())
                 .map(|(&x, &y)| x + strike * y)
                 .collect(),
-            design: CurveDesign {
-                color: Color::Green,
-                color_slot: index,
-                style: Style::Lines(LineEmphasis::Light),
-            },
-            name: Some(format!("{} {}", "\\tau=", tau)),
-        };
+            CurveDesign::new(Color::Green, index, Style::Lines(LineEmphasis::Light)),
+        )
+        .name(format!("{} {}", "\\tau=", tau));
 
         curves.push(curve);
     }
     // Make a dashed line at the strike price
-    let strike_price_curve = Curve {
-        x_coordinates: prices.iter().map(|_| strike).collect(),
-        y_coordinates: linspace(0.0, 5.0, number_of_prices).collect(),
-        design: CurveDesign {
-            color: Color::Grey,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Dashed),
-        },
-        name: Some(String::from("Strike")),
+    let strike_price_line = Annotation::VerticalLine {
+        x: strike,
+        design: RegionDesign::new(Color::Grey, MAIN_COLOR_SLOT),
+        emphasis: LineEmphasis::Dashed,
+        label: Some(String::from("K")),
     };
-    curves.push(strike_price_curve);
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("S"),
         y_label: String::from("V(S)"),
         bounds: (vec![price_start, price_end], vec![0.0, 5.0]),
+        ..Default::default()
+    };
+    //plot
+    Figure::new()
+        .curves(curves)
+        .annotation(strike_price_line)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+}
+
+/// Plot RMM portfolio value at a fixed price over strike and sigma
+#[allow(unused)]
+pub fn rmm_portfolio_value_surface(display: Display) {
+    let title = "\\text{RMM Portfolio Value over } K \\text{ and } \\sigma".to_string();
+    // Hold the price and time to maturity fixed and vary the strike and volatility
+    let price = 1_f64;
+    let tau = 1_f64;
+    let strikes = linspace(0.5, 2.0, 100).collect::<Vec<f64>>();
+    let sigmas = linspace(0.1, 1.0, 100).collect::<Vec<f64>>();
+    // Build one row of portfolio values per sigma
+    let z_values = sigmas
+        .iter()
+        .map(|sigma| {
+            strikes
+                .iter()
+                .map(|strike| rmm_cc_payoff(vec![price], *strike, *sigma, tau).1[0])
+                .collect::<Vec<f64>>()
+        })
+        .collect::<Vec<Vec<f64>>>();
+    let surface = Surface2D {
+        x_coordinates: strikes,
+        y_coordinates: sigmas,
+        z_values,
+        colormap: Colormap::sequential(Color::Purple),
+        contour_lines: Some(8),
+        label: String::from("V"),
+    };
+    // Build the plot's axes
+    let axes = Axes {
+        x_label: String::from("K"),
+        y_label: String::from("\\sigma"),
+        bounds: (vec![0.5, 2.0], vec![0.1, 1.0]),
+        ..Default::default()
     };
     //plot
-    transparent_plot(Some(curves), None, axes, title, display, None);
+    Figure::new()
+        .surface(surface)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 
 /// Leverage zones plot with S^2 pvf
@@ -356,63 +416,49 @@ pub fn leverage_zones_with_pvf(display: Display) {
     let number_of_points = 1000;
     let t = linspace(t_start, t_end, number_of_points).collect::<Vec<f64>>(); // Parameter for curves
                                                                               // Build the S^2 pvf
-    let curve = Curve {
-        x_coordinates: t.iter().map(|t| 5.0 * t).collect(),
-        y_coordinates: t.iter().map(|t| 25.0 * t * t).collect(),
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(String::from("V(S)=S^2")),
-    };
+    let curve = Curve::new(
+        t.iter().map(|t| 5.0 * t).collect(),
+        t.iter().map(|t| 25.0 * t * t).collect(),
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name("V(S)=S^2");
     // BUILD REGIONS
+    let line = t.iter().map(|t| 5.0 * t).collect::<Vec<f64>>();
     // y=x line and above (to y=5)
-    let over_levered = Region {
-        x_coordinates: (
-            t.iter().map(|t| 5.0 * t).collect(),
-            t.iter().map(|t| 5.0 * t).collect(),
-        ),
-        y_coordinates: (
-            t.iter().map(|t| 5.0 * t).collect(),
-            t.iter().map(|_| 5.0).collect(),
-        ),
-        design: RegionDesign {
-            color: Color::Purple,
-            color_slot: MAIN_COLOR_SLOT,
-        },
-        name: Some(String::from("Over-levered")),
-    };
+    let over_levered = Region::to_constant(
+        &line,
+        &line,
+        5.0,
+        RegionDesign::new(Color::Purple, MAIN_COLOR_SLOT),
+    )
+    .name("Over-levered");
     // y=x line and below (to y=0)
-    let under_levered = Region {
-        x_coordinates: (
-            t.iter().map(|t| 5.0 * t).collect(),
-            t.iter().map(|t| 5.0 * t).collect(),
-        ),
-        y_coordinates: (
-            t.iter().map(|t| 5.0 * t).collect(),
-            t.iter().map(|_| 0.0).collect(),
-        ),
-        design: RegionDesign {
-            color: Color::Blue,
-            color_slot: MAIN_COLOR_SLOT,
-        },
-        name: Some(String::from("Under-levered")),
-    };
+    let under_levered = Region::to_zero(
+        &line,
+        &line,
+        RegionDesign::new(Color::Blue, MAIN_COLOR_SLOT),
+    )
+    .name("Under-levered");
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("S"),
         y_label: String::from("V(S)"),
         bounds: (vec![0.0, 5.0], vec![0.0, 5.0]),
+        ..Default::default()
     };
     // plot
-    transparent_plot(
-        Some(vec![curve]),
-        Some(vec![over_levered, under_levered]),
-        axes,
-        title,
-        display, None
-    );
+    Figure::new()
+        .curve(curve)
+        .region(over_levered)
+        .region(under_levered)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 #[allow(unused)]
 /// plot brownian bridge
@@ -428,34 +474,71 @@ pub fn brownian_bridge_plotter(display: Display, start_price: f64, end_price: f6
         brownian_bridge_generator(start_price, end_price, t_end, number_of_points, 1.0, 4);
     let brownian2 =
         brownian_bridge_generator(start_price, end_price, t_end, number_of_points, 1.0, 33);
-    let curve1 = Curve {
-        x_coordinates: t.clone(),
-        y_coordinates: brownian1,
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some(String::from("\\text{High Volatility}")),
+    let curve1 = Curve::new(
+        t.clone(),
+        brownian1,
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{High Volatility}");
+    let curve2 = Curve::new(
+        t,
+        brownian2,
+        CurveDesign::new(Color::Green, 2, Style::Lines(LineEmphasis::Light)),
+    )
+    .name("\\text{Low Volatility}");
+    // Build the plot's axes
+    let axes = Axes {
+        x_label: String::from("t"),
+        y_label: String::from("P(t)"),
+        bounds: (vec![0.0, 1.0], vec![0.0, 3000.0]),
+        ..Default::default()
     };
-    let curve2 = Curve {
+    //plot
+    Figure::new()
+        .curve(curve1)
+        .curve(curve2)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+}
+
+/// Plot the quantiles of many brownian bridges as a fan chart
+#[allow(unused)]
+pub fn brownian_bridge_fan(display: Display, start_price: f64, end_price: f64) {
+    let title = "\\text{Price Path Quantiles}".to_string();
+    // Build an ensemble of brownian bridges, one per seed
+    let t_end = 1.0;
+    let number_of_points = 1000;
+    let t = linspace(0.0, t_end, number_of_points).collect::<Vec<f64>>();
+    let paths = (0..200)
+        .map(|seed| {
+            brownian_bridge_generator(start_price, end_price, t_end, number_of_points, 1.0, seed)
+        })
+        .collect::<Vec<Vec<f64>>>();
+    let fan_chart = FanChart {
         x_coordinates: t,
-        y_coordinates: brownian2,
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: 2,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some(String::from("\\text{Low Volatility}")),
+        paths,
+        color: Color::Green,
+        name: Some(String::from("\\text{Median}")),
     };
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("t"),
         y_label: String::from("P(t)"),
-        bounds: (vec![0.0, 1.0], vec![0.0, 3000.0]),
+        ..Default::default()
     };
     //plot
-    transparent_plot(Some(vec![curve1, curve2]), None, axes, title, display, None);
+    Figure::new()
+        .fan_chart(fan_chart)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 
 #[allow(unused)]
@@ -474,16 +557,17 @@ pub fn cubic_spline_plotter(display: Display) {
 
     // Get the CDF points
     let y_coordinates = standard_gaussian_cdf(x_coordinates.clone());
-    let curve = Curve {
-        x_coordinates: x_coordinates.clone(),
-        y_coordinates: y_coordinates.clone(),
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Markers(MarkerEmphasis::Heavy),
-        },
-        name: Some(String::from("\\text{CDF Points}")), // TODO: Make this just discrete points
-    };
+    // TODO: Make this just discrete points
+    let curve = Curve::new(
+        x_coordinates.clone(),
+        y_coordinates.clone(),
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Markers(MarkerEmphasis::Heavy),
+        ),
+    )
+    .name("\\text{CDF Points}");
 
     // Get the cubic spline
     let mut spline = MonotonicCubicSpline::new(&x_coordinates, &y_coordinates);
@@ -493,25 +577,32 @@ pub fn cubic_spline_plotter(display: Display) {
         .into_iter()
         .map(|x| spline.interpolate(x))
         .collect::<Vec<f64>>();
-    let spline_curve = Curve {
-        x_coordinates: x_spline_coordinates,
-        y_coordinates: y_spline_coordinates,
-        design: CurveDesign {
-            color: Color::Blue,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(String::from("\\text{CDF Spline}")),
-    };
+    let spline_curve = Curve::new(
+        x_spline_coordinates,
+        y_spline_coordinates,
+        CurveDesign::new(
+            Color::Blue,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name("\\text{CDF Spline}");
 
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("x"),
         y_label: String::from("\\Phi(x)"),
         bounds: (vec![-3.0, 3.0], vec![0.0, 1.0]),
+        ..Default::default()
     };
 
-    transparent_plot(Some(vec![curve, spline_curve]), None, axes, title, display, None);
+    Figure::new()
+        .curve(curve)
+        .curve(spline_curve)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 #[allow(unused)]
 /// Plot imported csv data for single column csv's
@@ -530,25 +621,31 @@ pub fn csv_plotter(display: Display) -> Result<(), Box<dyn Error>> {
     let t = linspace(t_start, t_end, number_of_points).collect::<Vec<f64>>();
 
     // Build curve
-    let curve = Curve {
-        x_coordinates: t,
-        y_coordinates: prices,
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some(String::from("\\text{Liquid Exchange Prices}")),
-    };
+    let curve = Curve::new(
+        t,
+        prices,
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{Liquid Exchange Prices}");
     // build plot axes and title
     let title = "\\text{csv Data}".to_string();
     let axes = Axes {
         x_label: String::from("\\text{Trade Number}"),
         y_label: String::from("\\text{Prices}"),
-        bounds: (vec![0.0, t_end], vec![0.95, 1.05]),
+        bounds: (vec![0.0, t_end], vec![]),
+        ..Default::default()
     };
     //plot
-    transparent_plot(Some(vec![curve]), None, axes, title, display, None);
+    Figure::new()
+        .curve(curve)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
     Ok(())
 }
 #[allow(unused)]
@@ -568,54 +665,66 @@ pub fn pp_and_cc_plotter(display: Display) -> Result<(), Box<dyn Error>> {
     // generate curves
     let cc = rmm_cc_payoff(x_coordinates.clone(), strike, sigma, tau).1;
     let pp = rmm_pp_payoff(x_coordinates.clone(), strike, sigma, tau).1;
-    let cc_curve = Curve {
-        x_coordinates: x_coordinates.clone(),
-        y_coordinates: cc.clone(),
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some(String::from("\\text{Covered Call}")),
-    };
-    let pp_curve = Curve {
-        x_coordinates: x_coordinates.clone(),
-        y_coordinates: pp.clone(),
-        design: CurveDesign {
-            color: Color::Blue,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some(String::from("\\text{Perpetual Put}")),
-    };
-    let both_curve = Curve {
+    let cc_curve = Curve::new(
+        x_coordinates.clone(),
+        cc.clone(),
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{Covered Call}");
+    let pp_curve = Curve::new(
+        x_coordinates.clone(),
+        pp.clone(),
+        CurveDesign::new(
+            Color::Blue,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{Perpetual Put}");
+    // Shade the prices where the covered call is worth more than the perpetual put
+    let mut cc_over_pp = Region::where_greater(
+        (&x_coordinates, &cc),
+        (&x_coordinates, &pp),
+        RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+    );
+    // Name only the first stretch so the legend has a single entry
+    if let Some(first) = cc_over_pp.first_mut() {
+        first.name = Some(String::from("\\text{Covered Call} > \\text{Perpetual Put}"));
+    }
+    let both_curve = Curve::new(
         x_coordinates,
-        y_coordinates: cc
-            .iter()
+        cc.iter()
             .zip(pp.iter())
             .map(|(&x, &y)| x + y)
             .collect::<Vec<_>>(),
-        design: CurveDesign {
-            color: Color::Purple,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some(String::from("\\text{Covered Call + Perpetual Put}")),
-    };
+        CurveDesign::new(
+            Color::Purple,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{Covered Call + Perpetual Put}");
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("S"),
         y_label: String::from("V(S)"),
         bounds: (vec![x_start, x_end], vec![0.0, 10.0]),
+        ..Default::default()
     };
     //plot
-    transparent_plot(
-        Some(vec![cc_curve, pp_curve, both_curve]),
-        None,
-        axes,
-        title,
-        display, None
-    );
+    Figure::new()
+        .curve(cc_curve)
+        .curve(pp_curve)
+        .curve(both_curve)
+        .regions(cc_over_pp)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
     Ok(())
 }
 
@@ -636,25 +745,68 @@ pub fn forced_rebalance(display: Display) -> Result<(), Box<dyn Error>> {
     let (reserves, value) =
         visualize::functions::forced_rebalance(x, strike, sigma, tau, ratio, inv);
     // Build curve
-    let curve = Curve {
-        x_coordinates: reserves,
-        y_coordinates: value,
-        design: CurveDesign {
-            color: Color::Purple,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some(String::from("\\text{Forced Rebalance}")),
-    };
+    let curve = Curve::new(
+        reserves,
+        value,
+        CurveDesign::new(
+            Color::Purple,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{Forced Rebalance}");
     // build plot axes and title
     let title = "\\text{Forced Rebalance}".to_string();
     let axes = Axes {
         x_label: String::from("R_x"),
         y_label: String::from("V(R_x)"),
         bounds: (vec![0.0, 1.0], vec![-1.0, 0.6]),
+        ..Default::default()
     };
     //plot
-    transparent_plot(Some(vec![curve]), None, axes, title, display, None);
+    Figure::new()
+        .curve(curve)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+    Ok(())
+}
+/// Plot the liquid exchange price aggregated into candles
+#[allow(unused)]
+pub fn simulation_candles(display: Display) -> Result<(), Box<dyn Error>> {
+    // Get the file information
+    let file_path = "uniswap_0.10000000000000002_0.csv";
+    let period = 10;
+
+    // Import the data from the csv file and aggregate it into one candle per period
+    let liquid_exchange_price_data = read_column_from_csv(file_path, "liquid_exchange_prices")?;
+    let candles = Candle::aggregate(&liquid_exchange_price_data, period);
+    let trade_number = (0..candles.len())
+        .map(|index| (index * period) as f64)
+        .collect::<Vec<f64>>();
+
+    let candlesticks = Candlesticks {
+        x_coordinates: trade_number,
+        candles,
+        increasing: RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+        decreasing: RegionDesign::new(Color::Purple, MAIN_COLOR_SLOT),
+        name: None,
+        legend_group: None,
+    };
+    let title = "\\text{Liquid Exchange Price}".to_string();
+    let axes = Axes {
+        x_label: "\\text{Trade Number}".to_string(),
+        y_label: "\\text{Price}".to_string(),
+        ..Default::default()
+    };
+    Figure::new()
+        .candlesticks(candlesticks)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+
     Ok(())
 }
 
@@ -676,45 +828,43 @@ pub fn simulation_arbitrage(display: Display) -> Result<(), Box<dyn Error>> {
     )
     .collect::<Vec<f64>>();
 
-    let liquid_exchange_price_curve = Curve {
-        x_coordinates: trade_number.clone(),
-        y_coordinates: liquid_exchange_price_data.clone(),
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some("\\text{Liquid Exchange Price}".to_string()),
-    };
-
-    let uniswap_price_curve = Curve {
-        x_coordinates: trade_number,
-        y_coordinates: uniswap_price_data,
-        design: CurveDesign {
-            color: Color::Purple,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some("\\text{Uniswap Price}".to_string()),
-    };
+    let liquid_exchange_price_curve = Curve::new(
+        trade_number.clone(),
+        liquid_exchange_price_data.clone(),
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{Liquid Exchange Price}");
+
+    let uniswap_price_curve = Curve::new(
+        trade_number,
+        uniswap_price_data,
+        CurveDesign::new(
+            Color::Purple,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{Uniswap Price}");
 
     let title = "\\text{Price Data}".to_string();
     let axes = Axes {
         x_label: "\\text{Trade Number}".to_string(),
         y_label: "\\text{Price}".to_string(),
-        bounds: (
-            vec![0.0, liquid_exchange_price_data.len() as f64],
-            vec![0.95, 1.05],
-        ),
+        bounds: (vec![0.0, liquid_exchange_price_data.len() as f64], vec![]),
+        ..Default::default()
     };
 
-    transparent_plot(
-        Some(vec![liquid_exchange_price_curve, uniswap_price_curve]),
-        None,
-        axes,
-        title,
-        display, None
-    );
+    Figure::new()
+        .curve(liquid_exchange_price_curve)
+        .curve(uniswap_price_curve)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
     Ok(())
 }
 
@@ -736,69 +886,191 @@ pub fn simulation_fee_growth(display: Display) -> Result<(), Box<dyn Error>> {
     )
     .collect::<Vec<f64>>();
 
-    let uniswap_x_reserves_curve = Curve {
-        x_coordinates: trade_number.clone(),
-        y_coordinates: uniswap_x_reserves.clone(),
-        design: CurveDesign {
-            color: Color::Blue,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some("\\text{X Reserves}".to_string()),
-    };
-
-    let uniswap_y_reserves_curve = Curve {
-        x_coordinates: trade_number.clone(),
-        y_coordinates: uniswap_y_reserves.clone(),
-        design: CurveDesign {
-            color: Color::Purple,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Light),
-        },
-        name: Some("\\text{Y Reserves}".to_string()),
-    };
+    let uniswap_x_reserves_curve = Curve::new(
+        trade_number.clone(),
+        uniswap_x_reserves.clone(),
+        CurveDesign::new(
+            Color::Blue,
+            MAIN_COLOR_SLOT,
+            Style::Steps(LineEmphasis::Light),
+        ),
+    )
+    .name("\\text{X Reserves}");
+
+    let uniswap_y_reserves_curve = Curve::new(
+        trade_number.clone(),
+        uniswap_y_reserves.clone(),
+        CurveDesign::new(
+            Color::Purple,
+            MAIN_COLOR_SLOT,
+            Style::Steps(LineEmphasis::DashDot),
+        ),
+    )
+    .name("\\text{Y Reserves}");
 
     let title = "\\text{Liquidity and Reserves}".to_string();
     let axes = Axes {
         x_label: "\\text{Trade Number}".to_string(),
         y_label: "\\text{Reserves}".to_string(),
-        bounds: (
-            vec![0.0, uniswap_x_reserves.len() as f64],
-            vec![990.0, 1020.0],
-        ),
+        bounds: (vec![0.0, uniswap_x_reserves.len() as f64], vec![]),
+        secondary_y: Some(SecondaryAxis {
+            label: "\\text{Liquidity}".to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
     };
 
     let liquidity = uniswap_x_reserves
         .iter()
         .zip(uniswap_y_reserves.iter())
-        .map(|(x, y)| x * y / 1e3)
+        .map(|(x, y)| x * y)
         .collect::<Vec<f64>>();
 
-    let liquidity_curve = Curve {
-        x_coordinates: trade_number,
-        y_coordinates: liquidity,
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some("\\text{Rescaled Liquidity}".to_string()),
-    };
+    let liquidity_curve = Curve::new(
+        trade_number,
+        liquidity,
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name("\\text{Liquidity}");
+
+    Figure::new()
+        .curve(uniswap_x_reserves_curve)
+        .curve(uniswap_y_reserves_curve)
+        .secondary_curve(liquidity_curve)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+    Ok(())
+}
 
-    transparent_plot(
-        Some(vec![
-            uniswap_x_reserves_curve,
-            uniswap_y_reserves_curve,
-            liquidity_curve,
-        ]),
-        None,
-        axes,
-        title,
-        display, None
-    );
+#[allow(unused)]
+/// Plot prices, reserves and liquidity of a simulation stacked over a shared trade number axis
+pub fn simulation_overview(display: Display) -> Result<(), Box<dyn Error>> {
+    // Get the file information
+    let file_path = "uniswap_0.10000000000000002_0.csv";
+
+    // Import the data from the csv file
+    let liquid_exchange_price_data = read_column_from_csv(file_path, "liquid_exchange_prices")?;
+    let uniswap_price_data = read_column_from_csv(file_path, "uniswap_prices")?;
+    let uniswap_x_reserves = read_column_from_csv(file_path, "uniswap_x_reserves")?;
+    let uniswap_y_reserves = read_column_from_csv(file_path, "uniswap_y_reserves")?;
+    let trade_number = linspace(
+        0.0,
+        liquid_exchange_price_data.len() as f64,
+        liquid_exchange_price_data.len(),
+    )
+    .collect::<Vec<f64>>();
+    let liquidity = uniswap_x_reserves
+        .iter()
+        .zip(uniswap_y_reserves.iter())
+        .map(|(x, y)| x * y)
+        .collect::<Vec<f64>>();
+
+    // Build one panel per quantity
+    let price_panel = Panel::new()
+        .curve(
+            Curve::new(
+                trade_number.clone(),
+                liquid_exchange_price_data,
+                CurveDesign::new(
+                    Color::Green,
+                    MAIN_COLOR_SLOT,
+                    Style::Lines(LineEmphasis::Light),
+                ),
+            )
+            .name("\\text{Liquid Exchange Price}"),
+        )
+        .curve(
+            Curve::new(
+                trade_number.clone(),
+                uniswap_price_data,
+                CurveDesign::new(
+                    Color::Purple,
+                    MAIN_COLOR_SLOT,
+                    Style::Lines(LineEmphasis::Light),
+                ),
+            )
+            .name("\\text{Uniswap Price}"),
+        )
+        .axes(Axes {
+            x_label: "\\text{Trade Number}".to_string(),
+            y_label: "\\text{Price}".to_string(),
+            bounds: (vec![0.0, trade_number.len() as f64], vec![]),
+            ..Default::default()
+        });
+    let reserves_panel = Panel::new()
+        .curve(
+            Curve::new(
+                trade_number.clone(),
+                uniswap_x_reserves,
+                CurveDesign::new(
+                    Color::Blue,
+                    MAIN_COLOR_SLOT,
+                    Style::Lines(LineEmphasis::Light),
+                ),
+            )
+            .name("\\text{X Reserves}"),
+        )
+        .curve(
+            Curve::new(
+                trade_number.clone(),
+                uniswap_y_reserves,
+                CurveDesign::new(
+                    Color::Purple,
+                    MAIN_COLOR_SLOT,
+                    Style::Lines(LineEmphasis::Light),
+                ),
+            )
+            .name("\\text{Y Reserves}"),
+        )
+        .axes(Axes {
+            x_label: "\\text{Trade Number}".to_string(),
+            y_label: "\\text{Reserves}".to_string(),
+            bounds: (vec![0.0, trade_number.len() as f64], vec![]),
+            ..Default::default()
+        });
+    let liquidity_panel = Panel::new()
+        .curve(
+            Curve::new(
+                trade_number.clone(),
+                liquidity,
+                CurveDesign::new(
+                    Color::Green,
+                    MAIN_COLOR_SLOT,
+                    Style::Lines(LineEmphasis::Heavy),
+                ),
+            )
+            .name("\\text{Liquidity}"),
+        )
+        .axes(Axes {
+            x_label: "\\text{Trade Number}".to_string(),
+            y_label: "\\text{Liquidity}".to_string(),
+            bounds: (vec![0.0, trade_number.len() as f64], vec![]),
+            ..Default::default()
+        });
+
+    Figure::new()
+        .panel(price_panel)
+        .panel(reserves_panel)
+        .panel(liquidity_panel)
+        .grid(Grid {
+            rows: 3,
+            columns: 1,
+            shared: SharedAxes::X,
+        })
+        .title("\\text{Simulation Overview}")
+        .display(display)
+        .plot();
     Ok(())
 }
 
+#[allow(unused)]
+/// Plot liquid exchange prices from several simulation runs
 pub fn simulation_price_paths(display: Display) -> Result<(), Box<dyn Error>> {
     // TODO: this file path is for the configuration file which will give information about how the files are named in the output directory.
     let output_path = "output";
@@ -821,29 +1093,17 @@ pub fn simulation_price_paths(display: Display) -> Result<(), Box<dyn Error>> {
         )
         .collect::<Vec<f64>>();
 
-        let liquid_exchange_price_curve = Curve {
-            x_coordinates: trade_number.clone(),
-            y_coordinates: liquid_exchange_price_data.clone()
-                [..liquid_exchange_price_data.len() - 1]
-                .to_vec(),
-            design: CurveDesign {
-                color: Color::Green,
-                color_slot: label,
-                style: Style::Lines(LineEmphasis::Heavy),
-            },
-            name: None,
-        };
-
-        // let uniswap_price_curve = Curve {
-        //     x_coordinates: trade_number,
-        //     y_coordinates: uniswap_price_data[..uniswap_price_data.len()-1].to_vec(),
-        //     design: CurveDesign {
-        //         color: Color::Purple,
-        //         color_slot: label,
-        //         style: Style::Lines(LineEmphasis::Light),
-        //     },
-        //     name: None,
-        // };
+        let liquid_exchange_price_curve = Curve::new(
+            trade_number.clone(),
+            liquid_exchange_price_data.clone()[..liquid_exchange_price_data.len() - 1].to_vec(),
+            CurveDesign::new(Color::Green, label, Style::Lines(LineEmphasis::Heavy)),
+        );
+
+        // let uniswap_price_curve = Curve::new(
+        //     trade_number,
+        //     uniswap_price_data[..uniswap_price_data.len()-1].to_vec(),
+        //     CurveDesign::new(Color::Purple, label, Style::Lines(LineEmphasis::Light)),
+        // );
 
         curves.push(liquid_exchange_price_curve);
         // curves.push(uniswap_price_curve);
@@ -853,18 +1113,241 @@ pub fn simulation_price_paths(display: Display) -> Result<(), Box<dyn Error>> {
     let axes = Axes {
         x_label: "\\text{Trade Number}".to_string(),
         y_label: "\\text{Price}".to_string(),
-        bounds: (
-            vec![0.0, curves[0].x_coordinates.len() as f64],
-            vec![0.8, 1.6],
+        bounds: (vec![0.0, curves[0].x_coordinates.len() as f64], vec![]),
+        ..Default::default()
+    };
+    Figure::new()
+        .curves(curves)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+
+    Ok(())
+}
+
+/// Plot the mean liquid exchange price across seeds with a band of one standard deviation
+#[allow(unused)]
+pub fn simulation_average_price(display: Display) -> Result<(), Box<dyn Error>> {
+    let output_path = "output";
+    let output_file_names = "uniswap";
+    let volatility = 0.08;
+    let mut paths = vec![];
+    for label in 0..10 {
+        let output_file = format!(
+            "{}/{}_{}_{}.csv",
+            output_path, output_file_names, volatility, label
+        );
+        paths.push(read_column_from_csv(
+            output_file.as_str(),
+            "liquid_exchange_prices",
+        )?);
+    }
+
+    // Average the seeds at every trade number they all reach
+    let trades = paths.iter().map(|path| path.len()).min().unwrap_or(0);
+    let seeds = paths.len() as f64;
+    let (means, deviations): (Vec<f64>, Vec<f64>) = (0..trades)
+        .map(|trade| {
+            let mean = paths.iter().map(|path| path[trade]).sum::<f64>() / seeds;
+            let variance = paths
+                .iter()
+                .map(|path| (path[trade] - mean).powi(2))
+                .sum::<f64>()
+                / seeds;
+            (mean, variance.sqrt())
+        })
+        .unzip();
+    let average_price_curve = Curve::new(
+        (0..trades).map(|trade| trade as f64).collect(),
+        means,
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
         ),
+    )
+    .name("\\text{Mean Price}")
+    .errors(CurveErrors {
+        y: Some(ErrorValues::Symmetric(deviations)),
+        ..Default::default()
+    });
+
+    let title = "\\text{Average Price}".to_string();
+    let axes = Axes {
+        x_label: "\\text{Trade Number}".to_string(),
+        y_label: "\\text{Price}".to_string(),
+        bounds: (vec![0.0, trades as f64], vec![]),
+        ..Default::default()
+    };
+    Figure::new()
+        .curve(average_price_curve)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+
+    Ok(())
+}
+
+/// Plot the spread of liquid exchange prices across seeds at a few points in the run
+#[allow(unused)]
+pub fn simulation_price_ensemble(display: Display) -> Result<(), Box<dyn Error>> {
+    let output_path = "output";
+    let output_file_names = "uniswap";
+    let volatility = 0.08;
+    let checkpoints = 5;
+    let mut paths = vec![];
+    for label in 0..10 {
+        let output_file = format!(
+            "{}/{}_{}_{}.csv",
+            output_path, output_file_names, volatility, label
+        );
+        paths.push(read_column_from_csv(
+            output_file.as_str(),
+            "liquid_exchange_prices",
+        )?);
+    }
+
+    // Group the price of every seed by the trade number it was sampled at
+    let trades = paths.iter().map(|path| path.len()).min().unwrap_or(0);
+    let samples = (1..=checkpoints)
+        .map(|checkpoint| {
+            let trade = checkpoint * (trades - 1) / checkpoints;
+            let prices = paths.iter().map(|path| path[trade]).collect::<Vec<f64>>();
+            (trade.to_string(), prices)
+        })
+        .collect::<Vec<(String, Vec<f64>)>>();
+    let distributions = Distributions {
+        samples,
+        style: DistributionStyle::Violin,
+        design: RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+        name: None,
+        legend_group: None,
     };
-    let display = Display {
-        transparent: true,
-        mode: DisplayMode::Dark,
-        show: true,
+
+    let title = "\\text{Price Ensemble}".to_string();
+    let axes = Axes {
+        x_label: "\\text{Trade Number}".to_string(),
+        y_label: "\\text{Price}".to_string(),
+        ..Default::default()
     };
+    Figure::new()
+        .distributions(distributions)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 
-    transparent_plot(Some(curves), None, axes, title, display);
+    Ok(())
+}
+
+/// Plot the distribution of terminal prices across simulation runs
+#[allow(unused)]
+pub fn simulation_terminal_prices(display: Display) -> Result<(), Box<dyn Error>> {
+    let output_path = "output";
+    let output_file_names = "uniswap";
+    let volatility = 0.08;
+    let mut terminal_prices = vec![];
+    for label in 0..10 {
+        let output_file = format!(
+            "{}/{}_{}_{}.csv",
+            output_path, output_file_names, volatility, label
+        );
+        let liquid_exchange_price_data =
+            read_column_from_csv(output_file.as_str(), "liquid_exchange_prices")?;
+        if let Some(price) = liquid_exchange_price_data.last() {
+            terminal_prices.push(*price);
+        }
+    }
+
+    let histogram = Histogram {
+        samples: terminal_prices,
+        bins: Bins::FreedmanDiaconis,
+        normalization: Normalization::Density,
+        design: RegionDesign::new(Color::Purple, MAIN_COLOR_SLOT),
+        density: Some(CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        )),
+        name: None,
+        legend_group: None,
+    };
+    let title = "\\text{Terminal Price Distribution}".to_string();
+    let axes = Axes {
+        x_label: "\\text{Terminal Price}".to_string(),
+        y_label: "\\text{Density}".to_string(),
+        ..Default::default()
+    };
+    Figure::new()
+        .histogram(histogram)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
+
+    Ok(())
+}
+
+/// Plot the growth of pool liquidity across volatility settings
+#[allow(unused)]
+pub fn simulation_liquidity_growth(display: Display) -> Result<(), Box<dyn Error>> {
+    let output_path = "output";
+    let output_file_names = "uniswap";
+    let volatilities = [0.04, 0.08, 0.12];
+    let runs = 10;
+    let mut categories = vec![];
+    let mut means = vec![];
+    let mut standard_errors = vec![];
+    for volatility in volatilities {
+        // The relative growth of the invariant sqrt(xy) over each run
+        let mut growths = vec![];
+        for label in 0..runs {
+            let output_file = format!(
+                "{}/{}_{}_{}.csv",
+                output_path, output_file_names, volatility, label
+            );
+            let x_reserves = read_column_from_csv(output_file.as_str(), "uniswap_x_reserves")?;
+            let y_reserves = read_column_from_csv(output_file.as_str(), "uniswap_y_reserves")?;
+            let liquidity = x_reserves
+                .iter()
+                .zip(y_reserves.iter())
+                .map(|(x, y)| (x * y).sqrt())
+                .collect::<Vec<f64>>();
+            if let (Some(first), Some(last)) = (liquidity.first(), liquidity.last()) {
+                growths.push(last / first - 1.0);
+            }
+        }
+        let n = growths.len() as f64;
+        let mean = growths.iter().sum::<f64>() / n;
+        let variance = growths.iter().map(|g| (g - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        categories.push(volatility.to_string());
+        means.push(100.0 * mean);
+        standard_errors.push(100.0 * (variance / n).sqrt());
+    }
+
+    let bars = Bars {
+        categories,
+        values: means,
+        errors: Some(standard_errors),
+        orientation: BarOrientation::Vertical,
+        design: RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+        name: None,
+        legend_group: None,
+    };
+    let title = "\\text{Liquidity Growth by Volatility}".to_string();
+    let axes = Axes {
+        x_label: "\\text{Volatility, }\\sigma".to_string(),
+        y_label: "\\text{Growth (\\%)}".to_string(),
+        ..Default::default()
+    };
+    Figure::new()
+        .bars(bars)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 
     Ok(())
 }
@@ -872,7 +1355,7 @@ pub fn simulation_price_paths(display: Display) -> Result<(), Box<dyn Error>> {
 #[allow(unused)]
 /// Plot RMM trading curve for multiple rescalings
 pub fn rmm_dfmm(display: Display) {
-    let title = format!("{}", "$\\text{Dynamic Function Market Maker}$");
+    let title = "$\\text{Dynamic Function Market Maker}$".to_string();
 
     // Define the range of prices
     let price_start = 0.0_f64;
@@ -888,19 +1371,19 @@ pub fn rmm_dfmm(display: Display) {
     let sigma = 0.5_f64;
     let tau = 2.0;
     let (x_scale, y_scale) = rmm_trading_curve(prices.clone(), strike, sigma, tau, Some(l));
-    let curve = Curve {
-        x_coordinates: x_scale,
-        y_coordinates: y_scale,
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(format!(
-            "{} {} {} {} {} {} {} {}",
-            "L = ", l, "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
-        )),
-    };
+    let curve = Curve::new(
+        x_scale,
+        y_scale,
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name(format!(
+        "{} {} {} {} {} {} {} {}",
+        "L = ", l, "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
+    ));
     curves.push(curve);
 
     // Second curve
@@ -909,19 +1392,19 @@ pub fn rmm_dfmm(display: Display) {
     let sigma = 0.5_f64;
     let tau = 2.0;
     let (x_scale, y_scale) = rmm_trading_curve(prices.clone(), strike, sigma, tau, Some(l));
-    let curve = Curve {
-        x_coordinates: x_scale,
-        y_coordinates: y_scale,
-        design: CurveDesign {
-            color: Color::Purple,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(format!(
-            "{} {} {} {} {} {} {} {}",
-            "L = ", l, "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
-        )),
-    };
+    let curve = Curve::new(
+        x_scale,
+        y_scale,
+        CurveDesign::new(
+            Color::Purple,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name(format!(
+        "{} {} {} {} {} {} {} {}",
+        "L = ", l, "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
+    ));
     curves.push(curve);
 
     // Third curve
@@ -930,50 +1413,53 @@ pub fn rmm_dfmm(display: Display) {
     let sigma = 1.25_f64;
     let tau = 2.0;
     let (x_scale, y_scale) = rmm_trading_curve(prices.clone(), strike, sigma, tau, Some(l));
-    let curve = Curve {
-        x_coordinates: x_scale,
-        y_coordinates: y_scale,
-        design: CurveDesign {
-            color: Color::Blue,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(format!(
-            "{} {} {} {} {} {} {} {}",
-            "L = ", l, "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
-        )),
-    };
+    let curve = Curve::new(
+        x_scale,
+        y_scale,
+        CurveDesign::new(
+            Color::Blue,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name(format!(
+        "{} {} {} {} {} {} {} {}",
+        "L = ", l, "\\quad K = ", strike, "\\quad \\sigma = ", sigma, "\\quad \\tau = ", tau
+    ));
     curves.push(curve);
 
     // Reserves
     let rx = 0.5;
     let ry = 0.24;
-    let point = Curve {
-        x_coordinates: vec![rx],
-        y_coordinates: vec![ry],
-        design: CurveDesign {
-            color: Color::Black,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Markers(MarkerEmphasis::Heavy),
-        },
-        name: Some(format!("{} {} {} {}", "R_X = ", rx, ", R_Y = ", ry)),
+    let point = Annotation::Point {
+        x: rx,
+        y: ry,
+        design: RegionDesign::new(Color::Black, MAIN_COLOR_SLOT),
+        emphasis: MarkerEmphasis::Heavy,
+        label: Some(format!("{} {} {} {}", "R_X = ", rx, ", R_Y = ", ry)),
     };
-    curves.push(point);
 
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("R_X"),
         y_label: String::from("R_Y"),
         bounds: (vec![0.0, 2.0], vec![0.0, 2.0]),
+        ..Default::default()
     };
     // plot
-    transparent_plot(Some(curves), None, axes, title, display);
+    Figure::new()
+        .curves(curves)
+        .annotation(point)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 
 #[allow(unused)]
 /// Plot RMM trading curve for multiple rescalings
 pub fn g3m_dfmm(display: Display) {
-    let title = format!("{}", "$\\text{Dynamic Function Market Maker}$");
+    let title = "$\\text{Dynamic Function Market Maker}$".to_string();
 
     // Define the range of x values
     let x_start = 0.01_f64;
@@ -988,79 +1474,82 @@ pub fn g3m_dfmm(display: Display) {
     let l = 1.0;
     let w = 0.2;
     let (x, y) = g3m_trading_curve(x_values.clone(), w, l);
-    let curve = Curve {
-        x_coordinates: x,
-        y_coordinates: y,
-        design: CurveDesign {
-            color: Color::Green,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(format!("{} {} {} {}", "L = ", l, "\\quad w = ", w)),
-    };
+    let curve = Curve::new(
+        x,
+        y,
+        CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name(format!("{} {} {} {}", "L = ", l, "\\quad w = ", w));
     curves.push(curve);
 
     // Second curve
     let l = 1.1642;
     let w = 0.5;
     let (x, y) = g3m_trading_curve(x_values.clone(), w, l);
-    let curve = Curve {
-        x_coordinates: x,
-        y_coordinates: y,
-        design: CurveDesign {
-            color: Color::Purple,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(format!("{} {} {} {}", "L \\approx ", l, "\\quad w = ", w)),
-    };
+    let curve = Curve::new(
+        x,
+        y,
+        CurveDesign::new(
+            Color::Purple,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name(format!("{} {} {} {}", "L \\approx ", l, "\\quad w = ", w));
     curves.push(curve);
 
     // Third curve
     let l = 1.3554;
     let w = 0.8;
     let (x, y) = g3m_trading_curve(x_values.clone(), w, l);
-    let curve = Curve {
-        x_coordinates: x,
-        y_coordinates: y,
-        design: CurveDesign {
-            color: Color::Blue,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Lines(LineEmphasis::Heavy),
-        },
-        name: Some(format!("{} {} {} {}", "L \\approx ", l, "\\quad w = ", w)),
-    };
+    let curve = Curve::new(
+        x,
+        y,
+        CurveDesign::new(
+            Color::Blue,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Heavy),
+        ),
+    )
+    .name(format!("{} {} {} {}", "L \\approx ", l, "\\quad w = ", w));
     curves.push(curve);
 
     // Reserves
     let rx = 1.5;
     let ry = 0.9036;
-    let point = Curve {
-        x_coordinates: vec![rx],
-        y_coordinates: vec![ry],
-        design: CurveDesign {
-            color: Color::Black,
-            color_slot: MAIN_COLOR_SLOT,
-            style: Style::Markers(MarkerEmphasis::Heavy),
-        },
-        name: Some(format!("{} {} {} {}", "R_X = ", rx, ", R_Y \\approx ", ry)),
+    let point = Annotation::Point {
+        x: rx,
+        y: ry,
+        design: RegionDesign::new(Color::Black, MAIN_COLOR_SLOT),
+        emphasis: MarkerEmphasis::Heavy,
+        label: Some(format!("{} {} {} {}", "R_X = ", rx, ", R_Y \\approx ", ry)),
     };
-    curves.push(point);
 
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("R_X"),
         y_label: String::from("R_Y"),
         bounds: (vec![0.0, 5.0], vec![0.0, 5.0]),
+        ..Default::default()
     };
     // plot
-    transparent_plot(Some(curves), None, axes, title, display);
+    Figure::new()
+        .curves(curves)
+        .annotation(point)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 
 #[allow(unused)]
 /// Plot RMM trading curve for multiple rescalings
 pub fn g3m_dfmm_cover(display: Display) {
-    let title = format!("{}", "$\\text{Dynamic Function Market Maker}$");
+    let title = "$\\text{Dynamic Function Market Maker}$".to_string();
 
     // Define the range of x values
     let x_start = 0.01_f64;
@@ -1076,16 +1565,11 @@ pub fn g3m_dfmm_cover(display: Display) {
 
     for (idx, w) in w_values.into_iter().enumerate() {
         let (x, y) = g3m_trading_curve(x_values.clone(), w, l);
-        let curve = Curve {
-            x_coordinates: x,
-            y_coordinates: y,
-            design: CurveDesign {
-                color: Color::Green,
-                color_slot: idx,
-                style: Style::Lines(LineEmphasis::Heavy),
-            },
-            name: None,
-        };
+        let curve = Curve::new(
+            x,
+            y,
+            CurveDesign::new(Color::Green, idx, Style::Lines(LineEmphasis::Heavy)),
+        );
         curves.push(curve);
     }
 
@@ -1094,10 +1578,16 @@ pub fn g3m_dfmm_cover(display: Display) {
         x_label: String::from(""),
         y_label: String::from(""),
         bounds: (vec![0.0, 5.0], vec![0.0, 5.0]),
+        ..Default::default()
     };
 
     // plot
-    transparent_plot(Some(curves), None, axes, title, display);
+    Figure::new()
+        .curves(curves)
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
 
 #[allow(unused)]
@@ -1112,9 +1602,9 @@ pub fn liquidity_tracker(display: Display) {
     let price_end = 10.0_f64;
     let number_of_prices = 1000;
     let prices = linspace(price_start, price_end, number_of_prices).collect::<Vec<f64>>();
-    // Build the curves
+    // Build the curves, colored by their strike
     let mut curves = vec![];
-    for (idx, (strike, sigma)) in strikes.into_iter().zip(sigmas.into_iter()).enumerate() {
+    for (strike, sigma) in strikes.into_iter().zip(sigmas) {
         let pdf_of_d_one = standard_gaussian_pdf(d_one(prices.clone(), strike, sigma, tau));
         let temp = pdf_of_d_one
             .iter()
@@ -1125,27 +1615,33 @@ pub fn liquidity_tracker(display: Display) {
             after_divide.push(y_val / prices[i]);
         }
 
-        let curve = Curve {
-            x_coordinates: prices.clone(),
-            y_coordinates: after_divide,
-            design: CurveDesign {
-                color: Color::Green,
-                color_slot: idx,
-                style: Style::Lines(LineEmphasis::Heavy),
-            },
-            name: Some(format!(
-                "{} {:.2} {} {:.2}",
-                "K = ", strike, "\\quad \\sigma = ", sigma
-            )),
-        };
-        curves.push(curve);
+        let curve = Curve::new(
+            prices.clone(),
+            after_divide,
+            CurveDesign::new(
+                Color::Green,
+                MAIN_COLOR_SLOT,
+                Style::Lines(LineEmphasis::Heavy),
+            ),
+        );
+        curves.push((strike, curve));
     }
     // Build the plot's axes
     let axes = Axes {
         x_label: String::from("\\textrm{Price, }S"),
         y_label: String::from("\\textrm{Depth}"),
         bounds: (vec![price_start, price_end], vec![0.0, 3.0]),
+        ..Default::default()
     };
     // plot
-    transparent_plot(Some(curves), None, axes, title, display);
+    Figure::new()
+        .curve_family(CurveFamily {
+            curves,
+            colormap: Colormap::sequential(Color::Green),
+            label: String::from("\\text{Strike } K"),
+        })
+        .axes(axes)
+        .title(title)
+        .display(display)
+        .plot();
 }
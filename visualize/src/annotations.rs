@@ -0,0 +1,348 @@
+//! Labels, reference lines, shaded spans and marked points drawn over a panel.
+
+#![warn(missing_docs)]
+use plotly::{
+    common::{Anchor, Font, Marker, Mode},
+    layout::{Annotation as LayoutAnnotation, Shape, ShapeLayer, ShapeLine, ShapeType},
+    Scatter,
+};
+
+use crate::{
+    design::*,
+    error::FigureError,
+    plot::{validate_color_slot, Scale, BAND_OPACITY},
+    theme::Skin,
+};
+
+/// How far an arrowed label sits from the point it points at, in pixels.
+const ARROW_OFFSET: f64 = 60.0;
+
+/// How far the label of a line, span or point sits from it, in pixels.
+const LABEL_SHIFT: f64 = 8.0;
+
+/// A mark that explains the data of a panel rather than being part of it.
+///
+/// Annotations follow the display mode and their text is typeset in LaTeX like legend entries.
+/// They are drawn over every trace of the panel, except for spans which are drawn underneath.
+#[derive(Clone, Debug)]
+pub enum Annotation {
+    /// A text label at a point, optionally set off to the upper right with an arrow to the point.
+    Label {
+        /// The x coordinate of the label, or of the point the arrow points at.
+        x: f64,
+        /// The y coordinate of the label, or of the point the arrow points at.
+        y: f64,
+        /// The LaTeX text of the label.
+        text: String,
+        /// Whether the label is set off with an arrow.
+        arrow: bool,
+    },
+    /// A line across the whole panel at a fixed y value.
+    HorizontalLine {
+        /// The y coordinate of the line.
+        y: f64,
+        /// The color of the line.
+        design: RegionDesign,
+        /// The width or dashing of the line.
+        emphasis: LineEmphasis,
+        /// An optional LaTeX label written at the right end of the line.
+        label: Option<String>,
+    },
+    /// A line up the whole panel at a fixed x value.
+    VerticalLine {
+        /// The x coordinate of the line.
+        x: f64,
+        /// The color of the line.
+        design: RegionDesign,
+        /// The width or dashing of the line.
+        emphasis: LineEmphasis,
+        /// An optional LaTeX label written at the top of the line.
+        label: Option<String>,
+    },
+    /// A translucent band up the whole panel between two x values.
+    Span {
+        /// The lower and upper x coordinates of the band.
+        x_range: (f64, f64),
+        /// The color of the band.
+        design: RegionDesign,
+        /// An optional LaTeX label written at the top of the band.
+        label: Option<String>,
+    },
+    /// A single marker at a point, kept out of the legend.
+    Point {
+        /// The x coordinate of the point.
+        x: f64,
+        /// The y coordinate of the point.
+        y: f64,
+        /// The color of the marker.
+        design: RegionDesign,
+        /// The size of the marker.
+        emphasis: MarkerEmphasis,
+        /// An optional LaTeX label written to the upper right of the point.
+        label: Option<String>,
+    },
+}
+
+impl Annotation {
+    /// Checks that the color exists.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        match self {
+            Annotation::Label { .. } => Ok(()),
+            Annotation::HorizontalLine { design, .. }
+            | Annotation::VerticalLine { design, .. }
+            | Annotation::Span { design, .. }
+            | Annotation::Point { design, .. } => {
+                validate_color_slot(&design.color, design.color_slot)
+            }
+        }
+    }
+
+    /// The x coordinates the annotation has to stay visible at, for fitting the x-axis.
+    pub(crate) fn x_values(&self) -> Vec<f64> {
+        match self {
+            Annotation::Label { x, .. }
+            | Annotation::VerticalLine { x, .. }
+            | Annotation::Point { x, .. } => vec![*x],
+            Annotation::Span { x_range, .. } => vec![x_range.0, x_range.1],
+            Annotation::HorizontalLine { .. } => vec![],
+        }
+    }
+
+    /// The y coordinates the annotation has to stay visible at, for fitting the y-axis.
+    pub(crate) fn y_values(&self) -> Vec<f64> {
+        match self {
+            Annotation::Label { y, .. }
+            | Annotation::HorizontalLine { y, .. }
+            | Annotation::Point { y, .. } => vec![*y],
+            Annotation::VerticalLine { .. } | Annotation::Span { .. } => vec![],
+        }
+    }
+
+    /// Builds the marker trace of a point, the only annotation drawn as a trace.
+    pub(crate) fn trace(&self, skin: Skin) -> Option<Box<Scatter<f64, f64>>> {
+        let Annotation::Point {
+            x,
+            y,
+            design,
+            emphasis,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let marker = Marker::new()
+            .color(design.stroke(skin))
+            .size(skin.marker_size(emphasis));
+        Some(
+            Scatter::new(vec![*x], vec![*y])
+                .mode(Mode::Markers)
+                .marker(marker)
+                .show_legend(false),
+        )
+    }
+
+    /// Builds the line or band of the annotation on the given axes, if it has one.
+    pub(crate) fn shape(&self, skin: Skin, x_axis: &str, y_axis: &str) -> Option<Shape> {
+        let line = |design: &RegionDesign, emphasis: &LineEmphasis| {
+            let line = ShapeLine::new()
+                .color(design.stroke(skin))
+                .width(skin.line_width(emphasis));
+            match emphasis.dash() {
+                Some(dash) => line.dash(dash),
+                None => line,
+            }
+        };
+        match self {
+            Annotation::HorizontalLine {
+                y,
+                design,
+                emphasis,
+                ..
+            } => Some(
+                Shape::new()
+                    .shape_type(ShapeType::Line)
+                    .x_ref(format!("{} domain", x_axis))
+                    .y_ref(y_axis)
+                    .x0(0.0)
+                    .x1(1.0)
+                    .y0(*y)
+                    .y1(*y)
+                    .line(line(design, emphasis)),
+            ),
+            Annotation::VerticalLine {
+                x,
+                design,
+                emphasis,
+                ..
+            } => Some(
+                Shape::new()
+                    .shape_type(ShapeType::Line)
+                    .x_ref(x_axis)
+                    .y_ref(format!("{} domain", y_axis))
+                    .x0(*x)
+                    .x1(*x)
+                    .y0(0.0)
+                    .y1(1.0)
+                    .line(line(design, emphasis)),
+            ),
+            Annotation::Span {
+                x_range, design, ..
+            } => Some(
+                Shape::new()
+                    .shape_type(ShapeType::Rect)
+                    .layer(ShapeLayer::Below)
+                    .x_ref(x_axis)
+                    .y_ref(format!("{} domain", y_axis))
+                    .x0(x_range.0)
+                    .x1(x_range.1)
+                    .y0(0.0)
+                    .y1(1.0)
+                    .fill_color(with_opacity(&design.stroke(skin), BAND_OPACITY))
+                    .line(ShapeLine::new().width(0.0)),
+            ),
+            Annotation::Label { .. } | Annotation::Point { .. } => None,
+        }
+    }
+
+    /// Builds the text of the annotation on the given axes, if it has any.
+    ///
+    /// Plotly places text in axis range coordinates, which are powers of ten on a log axis,
+    /// so coordinates on a log axis of the given x and y scales are taken to their logarithm.
+    pub(crate) fn text(
+        &self,
+        skin: Skin,
+        x_axis: &str,
+        y_axis: &str,
+        scales: (Scale, Scale),
+    ) -> Option<LayoutAnnotation> {
+        let position = |value: f64, scale: Scale| match scale {
+            Scale::Log => value.log10(),
+            _ => value,
+        };
+        let (x_position, y_position) = (
+            |x: f64| position(x, scales.0),
+            |y: f64| position(y, scales.1),
+        );
+        let text = |text: &str| {
+            LayoutAnnotation::new()
+                .text(skin.typeset(text, &skin.theme.latex.annotation))
+                .font(
+                    Font::new()
+                        .color(skin.foreground())
+                        .size(skin.theme.fonts.annotation),
+                )
+                .show_arrow(false)
+        };
+        match self {
+            Annotation::Label {
+                x,
+                y,
+                text: label,
+                arrow,
+            } => {
+                let annotation = text(label)
+                    .x_ref(x_axis)
+                    .y_ref(y_axis)
+                    .x(x_position(*x))
+                    .y(y_position(*y));
+                Some(match arrow {
+                    true => annotation
+                        .show_arrow(true)
+                        .arrow_color(skin.foreground())
+                        .arrow_head(2)
+                        .arrow_width(skin.theme.lines.light)
+                        .ax(ARROW_OFFSET)
+                        .ay(-ARROW_OFFSET),
+                    false => annotation,
+                })
+            }
+            Annotation::HorizontalLine {
+                y,
+                label: Some(label),
+                ..
+            } => Some(
+                text(label)
+                    .x_ref(format!("{} domain", x_axis))
+                    .y_ref(y_axis)
+                    .x(1.0)
+                    .y(y_position(*y))
+                    .x_anchor(Anchor::Right)
+                    .y_anchor(Anchor::Bottom)
+                    .y_shift(LABEL_SHIFT),
+            ),
+            Annotation::VerticalLine {
+                x,
+                label: Some(label),
+                ..
+            } => Some(
+                text(label)
+                    .x_ref(x_axis)
+                    .y_ref(format!("{} domain", y_axis))
+                    .x(x_position(*x))
+                    .y(1.0)
+                    .x_anchor(Anchor::Left)
+                    .y_anchor(Anchor::Top)
+                    .x_shift(LABEL_SHIFT),
+            ),
+            Annotation::Span {
+                x_range,
+                label: Some(label),
+                ..
+            } => Some(
+                text(label)
+                    .x_ref(x_axis)
+                    .y_ref(format!("{} domain", y_axis))
+                    .x((x_position(x_range.0) + x_position(x_range.1)) / 2.0)
+                    .y(1.0)
+                    .y_anchor(Anchor::Top),
+            ),
+            Annotation::Point {
+                x,
+                y,
+                label: Some(label),
+                ..
+            } => Some(
+                text(label)
+                    .x_ref(x_axis)
+                    .y_ref(y_axis)
+                    .x(x_position(*x))
+                    .y(y_position(*y))
+                    .x_anchor(Anchor::Left)
+                    .y_anchor(Anchor::Bottom)
+                    .x_shift(LABEL_SHIFT)
+                    .y_shift(LABEL_SHIFT),
+            ),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{TextMode, Theme};
+
+    #[test]
+    fn text_on_log_axes_is_placed_at_the_logarithm() {
+        let theme = Theme::default();
+        let skin = Skin {
+            theme: &theme,
+            mode: DisplayMode::Light,
+            text: TextMode::Raw,
+            grouped_legend: false,
+        };
+        let label = Annotation::Label {
+            x: 100.0,
+            y: 5.0,
+            text: String::from("label"),
+            arrow: false,
+        };
+        let position = |scales| {
+            let text = label.text(skin, "x", "y", scales).unwrap();
+            let json = serde_json::to_value(text).unwrap();
+            (json["x"].as_f64().unwrap(), json["y"].as_f64().unwrap())
+        };
+        assert_eq!(position((Scale::Linear, Scale::Linear)), (100.0, 5.0));
+        assert_eq!(position((Scale::Log, Scale::Linear)), (2.0, 5.0));
+    }
+}
@@ -0,0 +1,271 @@
+//! Bar charts of values per category.
+
+#![warn(missing_docs)]
+use plotly::{
+    common::{ErrorData, ErrorType, Line, Marker, Orientation},
+    Bar, Trace,
+};
+
+use crate::{
+    design::*,
+    error::FigureError,
+    plot::{legend_name, validate_color_slot, Grouped},
+    theme::Skin,
+};
+
+/// How bars of different series sharing a category are laid out.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum BarMode {
+    /// Side by side within each category.
+    #[default]
+    Grouped,
+    /// On top of each other, positive values upwards and negative values downwards.
+    Stacked,
+}
+
+/// Which way the bars point.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum BarOrientation {
+    /// Categories along the x-axis and values up the y-axis.
+    #[default]
+    Vertical,
+    /// Categories up the y-axis and values along the x-axis.
+    Horizontal,
+}
+
+/// A struct to hold one series of bars, one bar per category.
+pub struct Bars {
+    /// The category each bar is drawn at.
+    pub categories: Vec<String>,
+    /// The height of each bar.
+    pub values: Vec<f64>,
+    /// An optional symmetric error drawn on the end of each bar.
+    pub errors: Option<Vec<f64>>,
+    /// Which way the bars point.
+    pub orientation: BarOrientation,
+    /// The color of the bars.
+    pub design: RegionDesign,
+    /// An optional name for the series that appears in the legend.
+    pub name: Option<String>,
+    /// An optional legend group, so the bars toggle with other series of the same quantity.
+    pub legend_group: Option<String>,
+}
+
+impl Bars {
+    /// Checks that every category has a value and an error if errors are given.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        if self.categories.len() != self.values.len() {
+            return Err(FigureError::MismatchedCoordinates {
+                name: self.name.clone(),
+                x_len: self.categories.len(),
+                y_len: self.values.len(),
+            });
+        }
+        if let Some(errors) = &self.errors {
+            if errors.len() != self.values.len() {
+                return Err(FigureError::MismatchedErrors {
+                    name: self.name.clone(),
+                    values: self.values.len(),
+                    errors: errors.len(),
+                });
+            }
+        }
+        validate_color_slot(&self.design.color, self.design.color_slot)
+    }
+
+    /// Builds the bar trace for this series on the given axes.
+    pub(crate) fn trace(&self, skin: Skin, x_axis: &str, y_axis: &str) -> Box<dyn Trace> {
+        let marker = Marker::new()
+            .color(self.design.stroke(skin))
+            .line(Line::new().width(0.0));
+        let errors = self.errors.as_ref().map(|errors| {
+            ErrorData::new(ErrorType::Data)
+                .array(errors.clone())
+                .color(skin.foreground())
+                .thickness(skin.theme.lines.light)
+        });
+        let name = legend_name(&self.name, skin);
+        match self.orientation {
+            BarOrientation::Vertical => {
+                let trace = Bar::new(self.categories.clone(), self.values.clone())
+                    .orientation(Orientation::Vertical)
+                    .marker(marker)
+                    .name(&name)
+                    .show_legend(self.name.is_some())
+                    .x_axis(x_axis)
+                    .y_axis(y_axis);
+                let trace = trace.grouped(&self.legend_group, &self.name, skin);
+                match errors {
+                    Some(errors) => trace.error_y(errors),
+                    None => trace,
+                }
+            }
+            BarOrientation::Horizontal => {
+                let trace = Bar::new(self.values.clone(), self.categories.clone())
+                    .orientation(Orientation::Horizontal)
+                    .marker(marker)
+                    .name(&name)
+                    .show_legend(self.name.is_some())
+                    .x_axis(x_axis)
+                    .y_axis(y_axis);
+                let trace = trace.grouped(&self.legend_group, &self.name, skin);
+                match errors {
+                    Some(errors) => trace.error_x(errors),
+                    None => trace,
+                }
+            }
+        }
+    }
+}
+
+/// The values the value axis has to show for the bars pointing in `orientation`.
+///
+/// Bars always stand on zero, and stacked bars reach as far as the sum of their category.
+/// Error bars are included, on the end of each stacked segment.
+pub(crate) fn value_extents(bars: &[Bars], mode: BarMode, orientation: BarOrientation) -> Vec<f64> {
+    let bars = bars
+        .iter()
+        .filter(|bars| bars.orientation == orientation)
+        .collect::<Vec<&Bars>>();
+    if bars.is_empty() {
+        return vec![];
+    }
+    let mut extents = vec![0.0];
+    match mode {
+        BarMode::Grouped => {
+            for series in bars.iter() {
+                let errors = series.errors.clone().unwrap_or_default();
+                for (index, value) in series.values.iter().enumerate() {
+                    let error = errors.get(index).cloned().unwrap_or(0.0);
+                    extents.extend([value - error, value + error]);
+                }
+            }
+        }
+        BarMode::Stacked => {
+            let mut totals: Vec<(&String, f64, f64)> = vec![];
+            for series in bars.iter() {
+                let errors = series.errors.clone().unwrap_or_default();
+                for (index, (category, value)) in series
+                    .categories
+                    .iter()
+                    .zip(series.values.iter())
+                    .enumerate()
+                {
+                    let position = match totals.iter().position(|(seen, _, _)| *seen == category) {
+                        Some(position) => position,
+                        None => {
+                            totals.push((category, 0.0, 0.0));
+                            totals.len() - 1
+                        }
+                    };
+                    let total = match *value >= 0.0 {
+                        true => &mut totals[position].1,
+                        false => &mut totals[position].2,
+                    };
+                    *total += value;
+                    // The error bar of a segment sits on its end, the running total.
+                    let error = errors.get(index).cloned().unwrap_or(0.0);
+                    extents.extend([*total - error, *total + error]);
+                }
+            }
+        }
+    }
+    extents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{TextMode, Theme};
+
+    fn bars(values: Vec<f64>, errors: Option<Vec<f64>>, orientation: BarOrientation) -> Bars {
+        Bars {
+            categories: (0..values.len()).map(|index| index.to_string()).collect(),
+            values,
+            errors,
+            orientation,
+            design: RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+            name: None,
+            legend_group: None,
+        }
+    }
+
+    fn range(extents: Vec<f64>) -> (f64, f64) {
+        let lowest = extents.iter().cloned().fold(f64::INFINITY, f64::min);
+        let highest = extents.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        (lowest, highest)
+    }
+
+    #[test]
+    fn grouped_extents_reach_the_error_bars() {
+        let series = [
+            bars(
+                vec![2.0, -1.0],
+                Some(vec![0.5, 0.25]),
+                BarOrientation::Vertical,
+            ),
+            bars(vec![3.0, 1.0], None, BarOrientation::Vertical),
+        ];
+        let extents = value_extents(&series, BarMode::Grouped, BarOrientation::Vertical);
+        assert_eq!(range(extents), (-1.25, 3.0));
+    }
+
+    #[test]
+    fn stacked_extents_reach_the_totals_and_their_error_bars() {
+        let series = [
+            bars(
+                vec![2.0, -1.0],
+                Some(vec![0.5, 0.5]),
+                BarOrientation::Vertical,
+            ),
+            bars(
+                vec![3.0, -2.0],
+                Some(vec![1.0, 0.25]),
+                BarOrientation::Vertical,
+            ),
+        ];
+        let extents = value_extents(&series, BarMode::Stacked, BarOrientation::Vertical);
+        assert_eq!(range(extents), (-3.25, 6.0));
+        let positive = [bars(vec![1.0], None, BarOrientation::Vertical)];
+        let extents = value_extents(&positive, BarMode::Stacked, BarOrientation::Vertical);
+        assert_eq!(range(extents), (0.0, 1.0));
+    }
+
+    #[test]
+    fn extents_only_count_bars_of_the_orientation() {
+        let series = [
+            bars(vec![5.0], None, BarOrientation::Vertical),
+            bars(vec![-4.0], None, BarOrientation::Horizontal),
+        ];
+        let horizontal = value_extents(&series, BarMode::Grouped, BarOrientation::Horizontal);
+        assert_eq!(range(horizontal), (-4.0, 0.0));
+        assert!(value_extents(&series[1..], BarMode::Grouped, BarOrientation::Vertical).is_empty());
+    }
+
+    #[test]
+    fn horizontal_bars_put_the_values_along_x() {
+        let theme = Theme::default();
+        let skin = Skin {
+            theme: &theme,
+            mode: DisplayMode::Light,
+            text: TextMode::Raw,
+            grouped_legend: false,
+        };
+        let series = bars(
+            vec![2.0, 3.0],
+            Some(vec![0.5, 0.5]),
+            BarOrientation::Horizontal,
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&series.trace(skin, "x2", "y2").to_json()).unwrap();
+        assert_eq!(json["orientation"], "h");
+        assert_eq!(json["x"], serde_json::json!([2.0, 3.0]));
+        assert_eq!(json["y"], serde_json::json!(["0", "1"]));
+        assert_eq!(json["error_x"]["array"], serde_json::json!([0.5, 0.5]));
+        assert!(json["error_y"].is_null());
+        assert_eq!(
+            (&json["xaxis"], &json["yaxis"]),
+            (&"x2".into(), &"y2".into())
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Candlestick charts of prices aggregated into open, high, low and close candles.
+
+#![warn(missing_docs)]
+use plotly::{
+    common::{Direction, Line},
+    Candlestick,
+};
+
+use crate::{
+    design::*,
+    error::FigureError,
+    plot::{legend_name, validate_color_slot, Grouped},
+    theme::Skin,
+};
+
+/// The open, high, low and close of a price over one period.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Candle {
+    /// The first price of the period.
+    pub open: f64,
+    /// The highest price of the period.
+    pub high: f64,
+    /// The lowest price of the period.
+    pub low: f64,
+    /// The last price of the period.
+    pub close: f64,
+}
+
+impl Candle {
+    /// Aggregates tick level prices into one candle per `period` consecutive prices.
+    ///
+    /// The last candle covers whatever prices are left over, and a period of zero is taken as one.
+    /// Prices that are not finite are skipped, and a period without any finite price becomes a
+    /// candle of NaN so the candles stay aligned with their periods.
+    pub fn aggregate(prices: &[f64], period: usize) -> Vec<Candle> {
+        prices
+            .chunks(period.max(1))
+            .map(|chunk| {
+                let finite = chunk
+                    .iter()
+                    .cloned()
+                    .filter(|price| price.is_finite())
+                    .collect::<Vec<f64>>();
+                match (finite.first(), finite.last()) {
+                    (Some(open), Some(close)) => Candle {
+                        open: *open,
+                        high: finite.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                        low: finite.iter().cloned().fold(f64::INFINITY, f64::min),
+                        close: *close,
+                    },
+                    _ => Candle {
+                        open: f64::NAN,
+                        high: f64::NAN,
+                        low: f64::NAN,
+                        close: f64::NAN,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// A struct to hold a series of candles drawn as a candlestick chart.
+pub struct Candlesticks {
+    /// The x coordinate each candle is drawn at, usually the start of its period.
+    pub x_coordinates: Vec<f64>,
+    /// The candles, one per x coordinate.
+    pub candles: Vec<Candle>,
+    /// The color of candles that close above their open.
+    pub increasing: RegionDesign,
+    /// The color of candles that close below their open.
+    pub decreasing: RegionDesign,
+    /// An optional name for the series that appears in the legend.
+    pub name: Option<String>,
+    /// An optional legend group, so the candlesticks toggle with the overlays drawn on them.
+    pub legend_group: Option<String>,
+}
+
+impl Candlesticks {
+    /// Checks that every candle has an x coordinate and both colors exist.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        if self.x_coordinates.len() != self.candles.len() {
+            return Err(FigureError::MismatchedCoordinates {
+                name: self.name.clone(),
+                x_len: self.x_coordinates.len(),
+                y_len: self.candles.len(),
+            });
+        }
+        validate_color_slot(&self.increasing.color, self.increasing.color_slot)?;
+        validate_color_slot(&self.decreasing.color, self.decreasing.color_slot)
+    }
+
+    /// Every price the candles reach, for fitting the y-axis.
+    pub(crate) fn y_values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.candles
+            .iter()
+            .flat_map(|candle| [candle.high, candle.low])
+    }
+
+    /// Builds the candlestick trace for this series on the given axes.
+    pub(crate) fn trace(
+        &self,
+        skin: Skin,
+        x_axis: &str,
+        y_axis: &str,
+    ) -> Box<Candlestick<f64, f64>> {
+        let prices = |price: fn(&Candle) -> f64| self.candles.iter().map(price).collect();
+        let line = |design: &RegionDesign| {
+            Line::new()
+                .color(design.stroke(skin))
+                .width(skin.theme.lines.light)
+        };
+        let trace = Candlestick::new(
+            self.x_coordinates.clone(),
+            prices(|candle| candle.open),
+            prices(|candle| candle.high),
+            prices(|candle| candle.low),
+            prices(|candle| candle.close),
+        )
+        .increasing(Direction::Increasing {
+            line: line(&self.increasing),
+        })
+        .decreasing(Direction::Decreasing {
+            line: line(&self.decreasing),
+        })
+        .name(legend_name(&self.name, skin))
+        .show_legend(self.name.is_some())
+        .x_axis(x_axis)
+        .y_axis(y_axis);
+        Box::new(trace.grouped(&self.legend_group, &self.name, skin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    #[test]
+    fn aggregate_covers_a_short_last_chunk() {
+        let candles = Candle::aggregate(&[1.0, 3.0, 2.0, 5.0, 4.0], 2);
+        assert_eq!(
+            candles,
+            vec![
+                candle(1.0, 3.0, 1.0, 3.0),
+                candle(2.0, 5.0, 2.0, 5.0),
+                candle(4.0, 4.0, 4.0, 4.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregate_takes_a_period_of_zero_as_one() {
+        let candles = Candle::aggregate(&[1.0, 2.0], 0);
+        assert_eq!(
+            candles,
+            vec![candle(1.0, 1.0, 1.0, 1.0), candle(2.0, 2.0, 2.0, 2.0)]
+        );
+        assert!(Candle::aggregate(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn aggregate_skips_prices_that_are_not_finite() {
+        let candles = Candle::aggregate(&[f64::NAN, 2.0, 4.0, f64::NAN, f64::NAN, f64::NAN], 3);
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0], candle(2.0, 4.0, 2.0, 4.0));
+        let empty = candles[1];
+        assert!(empty.open.is_nan() && empty.high.is_nan());
+        assert!(empty.low.is_nan() && empty.close.is_nan());
+    }
+}
@@ -0,0 +1,298 @@
+//! Continuous colormaps interpolated from the Primitive ramps.
+
+#![warn(missing_docs)]
+use plotly::{
+    common::{ColorBar, ColorScale, ColorScaleElement, Font, HoverInfo, Marker, Mode, Title},
+    Scatter,
+};
+
+use crate::{design::*, error::FigureError, plot::Curve, theme::Skin};
+
+/// The number of stops a plotly colorscale is sampled at.
+const COLOR_SCALE_STEPS: usize = 16;
+
+/// A continuous map from the unit interval to branded colors.
+///
+/// Stops are given as a color and slot in dark mode terms and resolved per display mode,
+/// so a colormap keeps its contrast against either background like any other slot does.
+#[derive(Clone, Debug)]
+pub struct Colormap {
+    stops: Vec<(f64, Color, usize)>,
+}
+
+impl Colormap {
+    /// A single hue running from a dim slot at zero to a bright slot at one.
+    pub fn sequential(color: Color) -> Self {
+        let stops = (1..=8)
+            .rev()
+            .map(|slot| ((8 - slot) as f64 / 7.0, color, slot))
+            .collect();
+        Self { stops }
+    }
+
+    /// Two hues meeting at a dim grey in the middle, e.g. purple below and green above.
+    pub fn diverging(low: Color, high: Color) -> Self {
+        Self {
+            stops: vec![
+                (0.0, low, 1),
+                (0.25, low, 4),
+                (0.5, Color::Grey, 7),
+                (0.75, high, 4),
+                (1.0, high, 1),
+            ],
+        }
+    }
+
+    /// The same colormap running in the opposite direction.
+    pub fn reversed(&self) -> Self {
+        Self {
+            stops: self
+                .stops
+                .iter()
+                .rev()
+                .map(|(position, color, slot)| (1.0 - position, *color, *slot))
+                .collect(),
+        }
+    }
+
+    /// The hex color at `t`, clamped to the unit interval.
+    pub fn at(&self, t: f64, skin: Skin) -> String {
+        let t = match t.is_nan() {
+            true => 0.0,
+            false => t.clamp(0.0, 1.0),
+        };
+        let upper = self
+            .stops
+            .iter()
+            .position(|(position, _, _)| *position >= t)
+            .unwrap_or(self.stops.len() - 1)
+            .max(1);
+        let (start, start_color, start_slot) = self.stops[upper - 1];
+        let (end, end_color, end_slot) = self.stops[upper];
+        let weight = match end > start {
+            true => (t - start) / (end - start),
+            false => 0.0,
+        };
+        let start = rgb(&skin.resolve(&start_color, start_slot));
+        let end = rgb(&skin.resolve(&end_color, end_slot));
+        start
+            .iter()
+            .zip(end.iter())
+            .map(|(start, end)| {
+                let channel = *start as f64 + (*end as f64 - *start as f64) * weight;
+                format!("{:02X}", channel.round() as u8)
+            })
+            .collect()
+    }
+
+    /// The colormap sampled as a plotly colorscale for the given display mode.
+    pub fn color_scale(&self, skin: Skin) -> ColorScale {
+        ColorScale::Vector(
+            (0..COLOR_SCALE_STEPS)
+                .map(|step| {
+                    let t = step as f64 / (COLOR_SCALE_STEPS - 1) as f64;
+                    ColorScaleElement(t, self.at(t, skin))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Splits a six digit hex color into its red, green and blue channels.
+fn rgb(hex: &str) -> [u8; 3] {
+    let channel =
+        |index: usize| u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).unwrap_or(0);
+    [channel(0), channel(1), channel(2)]
+}
+
+/// Curves colored by a numeric parameter such as strike or volatility, with a colorbar legend.
+///
+/// The design color of each curve is replaced by the colormap at its parameter,
+/// normalized over the smallest and largest parameter in the family.
+pub struct CurveFamily {
+    /// The curves paired with the parameter that colors them.
+    pub curves: Vec<(f64, Curve)>,
+    /// The colormap the parameters are drawn from.
+    pub colormap: Colormap,
+    /// The title of the colorbar.
+    pub label: String,
+}
+
+impl CurveFamily {
+    /// Checks every curve in the family.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        for (_, curve) in self.curves.iter() {
+            curve.validate()?;
+        }
+        Ok(())
+    }
+
+    /// The smallest and largest parameter in the family.
+    fn parameter_range(&self) -> (f64, f64) {
+        self.curves.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(lowest, highest), (parameter, _)| (lowest.min(*parameter), highest.max(*parameter)),
+        )
+    }
+
+    /// Builds the traces of every curve, colored by its parameter.
+    pub(crate) fn traces<'a>(
+        &'a self,
+        skin: Skin<'a>,
+    ) -> impl Iterator<Item = Box<Scatter<f64, f64>>> + 'a {
+        let (lowest, highest) = self.parameter_range();
+        self.curves.iter().flat_map(move |(parameter, curve)| {
+            let t = match highest > lowest {
+                true => (parameter - lowest) / (highest - lowest),
+                false => 0.5,
+            };
+            curve.traces_with_color(&self.colormap.at(t, skin), skin)
+        })
+    }
+
+    /// Builds an invisible trace that only carries the colorbar, placed `x` across the figure.
+    pub(crate) fn color_bar(&self, skin: Skin, x: f64) -> Box<Scatter<f64, f64>> {
+        let (lowest, highest) = self.parameter_range();
+        Scatter::new(vec![f64::NAN], vec![f64::NAN])
+            .mode(Mode::Markers)
+            .marker(
+                Marker::new()
+                    .color_scale(self.colormap.color_scale(skin))
+                    .cmin(lowest)
+                    .cmax(highest)
+                    .show_scale(true)
+                    .color_bar(color_bar(&self.label, skin, x)),
+            )
+            .hover_info(HoverInfo::Skip)
+            .show_legend(false)
+    }
+}
+
+/// A branded colorbar with a title and ticks in the text mode, placed `x` across the figure.
+pub(crate) fn color_bar(label: &str, skin: Skin, x: f64) -> ColorBar {
+    let latex = &skin.theme.latex;
+    let title = skin.typeset(label, &latex.axis_label);
+    let color_bar = ColorBar::new()
+        .title(Title::new(&title).font(Font::new().size(skin.theme.fonts.color_bar)));
+    let color_bar = match skin.latex_ticks() {
+        true => color_bar
+            .tick_prefix(&format!("$\\{}{{", latex.color_bar_ticks))
+            .tick_suffix("}$"),
+        false => color_bar,
+    };
+    color_bar
+        .tick_font(
+            Font::new()
+                .size(skin.theme.fonts.ticks)
+                .color(skin.foreground()),
+        )
+        .outline_width(0)
+        .thickness(20)
+        .x(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{TextMode, Theme};
+
+    fn skin(theme: &Theme) -> Skin<'_> {
+        Skin {
+            theme,
+            mode: DisplayMode::Dark,
+            text: TextMode::Raw,
+            grouped_legend: false,
+        }
+    }
+
+    #[test]
+    fn colormaps_hit_their_stops() {
+        let theme = Theme::default();
+        let skin = skin(&theme);
+        let diverging = Colormap::diverging(Color::Purple, Color::Green);
+        assert_eq!(diverging.at(0.0, skin), skin.resolve(&Color::Purple, 1));
+        assert_eq!(diverging.at(0.25, skin), skin.resolve(&Color::Purple, 4));
+        assert_eq!(diverging.at(0.5, skin), skin.resolve(&Color::Grey, 7));
+        assert_eq!(diverging.at(0.75, skin), skin.resolve(&Color::Green, 4));
+        assert_eq!(diverging.at(1.0, skin), skin.resolve(&Color::Green, 1));
+        let sequential = Colormap::sequential(Color::Blue);
+        assert_eq!(sequential.at(0.0, skin), skin.resolve(&Color::Blue, 8));
+        assert_eq!(sequential.at(1.0, skin), skin.resolve(&Color::Blue, 1));
+    }
+
+    #[test]
+    fn colormaps_interpolate_between_stops() {
+        let theme = Theme::default();
+        let skin = skin(&theme);
+        let diverging = Colormap::diverging(Color::Purple, Color::Green);
+        let (low, high) = (
+            rgb(&skin.resolve(&Color::Purple, 1)),
+            rgb(&skin.resolve(&Color::Purple, 4)),
+        );
+        let expected = |weight: f64| {
+            low.iter()
+                .zip(high.iter())
+                .map(|(low, high)| {
+                    let channel = *low as f64 + (*high as f64 - *low as f64) * weight;
+                    format!("{:02X}", channel.round() as u8)
+                })
+                .collect::<String>()
+        };
+        assert_eq!(diverging.at(0.125, skin), expected(0.5));
+        assert_eq!(diverging.at(0.0625, skin), expected(0.25));
+    }
+
+    #[test]
+    fn colormaps_clamp_and_reverse() {
+        let theme = Theme::default();
+        let skin = skin(&theme);
+        let colormap = Colormap::diverging(Color::Blue, Color::Green);
+        assert_eq!(colormap.at(-1.0, skin), colormap.at(0.0, skin));
+        assert_eq!(colormap.at(2.0, skin), colormap.at(1.0, skin));
+        assert_eq!(colormap.at(f64::NAN, skin), colormap.at(0.0, skin));
+        let reversed = colormap.reversed();
+        for t in [0.0, 0.1, 0.25, 0.4, 0.5, 0.8, 1.0] {
+            assert_eq!(reversed.at(t, skin), colormap.at(1.0 - t, skin));
+        }
+    }
+
+    fn family_colors(parameters: &[f64], skin: Skin) -> Vec<String> {
+        let design = CurveDesign::new(Color::Grey, 5, Style::Lines(LineEmphasis::Light));
+        let family = CurveFamily {
+            curves: parameters
+                .iter()
+                .map(|parameter| {
+                    let curve = Curve::new(vec![0.0, 1.0], vec![0.0, 1.0], design.clone());
+                    (*parameter, curve)
+                })
+                .collect(),
+            colormap: Colormap::sequential(Color::Green),
+            label: String::from("strike"),
+        };
+        family
+            .traces(skin)
+            .map(|trace| {
+                let json = serde_json::to_value(trace).unwrap();
+                json["line"]["color"].as_str().unwrap().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn families_normalize_their_parameters() {
+        let theme = Theme::default();
+        let skin = skin(&theme);
+        let colormap = Colormap::sequential(Color::Green);
+        let colors = family_colors(&[10.0, 30.0, 20.0], skin);
+        assert_eq!(
+            colors,
+            vec![
+                colormap.at(0.0, skin),
+                colormap.at(1.0, skin),
+                colormap.at(0.5, skin)
+            ]
+        );
+        let equal = family_colors(&[5.0, 5.0], skin);
+        assert_eq!(equal, vec![colormap.at(0.5, skin); 2]);
+    }
+}
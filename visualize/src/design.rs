@@ -1,11 +1,15 @@
-#[derive(Copy, Clone)]
+use plotly::common::{DashType, LineShape, MarkerSymbol};
+
+use crate::{error::FigureError, theme::Skin};
+
+#[derive(Copy, Clone, Debug)]
 #[allow(unused)]
 pub enum DisplayMode {
     Light,
     Dark,
 }
-// TODO: Use DisplayMode for light mode/dark mode. For light mode, we will also want to consider decreasing from the top when using a list of colors since that will be darker
-#[derive(Debug)]
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[allow(unused)]
 pub enum Color {
     Green,
@@ -14,42 +18,375 @@ pub enum Color {
     Grey,
     Black,
     White,
+    /// A color outside of the brand palette, for co-branded charts.
+    Custom(CustomColor),
+}
+
+impl Color {
+    /// The ten step ramp behind this color, or `None` for black, white and custom colors.
+    pub fn ramp(&self) -> Option<&'static [&'static str; 10]> {
+        match self {
+            Color::Green => Some(&PRIMITIVE_GREENS),
+            Color::Blue => Some(&PRIMITIVE_BLUES),
+            Color::Purple => Some(&PRIMITIVE_PURPLES),
+            Color::Grey => Some(&PRIMITIVE_GREYS),
+            Color::Black | Color::White | Color::Custom(_) => None,
+        }
+    }
+}
+
+/// A color given as red, green and blue channels with an alpha between zero and one.
+///
+/// The constructors check their input, so a custom color is always valid once built.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CustomColor {
+    red: u8,
+    green: u8,
+    blue: u8,
+    alpha: f64,
+}
+
+impl CustomColor {
+    /// An opaque color from its red, green and blue channels.
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha: 1.0,
+        }
+    }
+
+    /// A color from its red, green and blue channels and an alpha between zero and one.
+    pub fn rgba(red: u8, green: u8, blue: u8, alpha: f64) -> Result<Self, FigureError> {
+        let alpha = Opacity::new(alpha)?.value();
+        Ok(Self {
+            red,
+            green,
+            blue,
+            alpha,
+        })
+    }
+
+    /// A color from a hex string of three, six or eight digits, with or without a leading `#`.
+    pub fn hex(hex: &str) -> Result<Self, FigureError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let invalid = || FigureError::InvalidColor(hex.to_string());
+        if !digits.chars().all(|digit| digit.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+        match digits.len() {
+            3 => {
+                let doubled = |index: usize| channel(&digits[index..index + 1].repeat(2));
+                Ok(Self::rgb(doubled(0)?, doubled(1)?, doubled(2)?))
+            }
+            6 | 8 => {
+                let alpha = match digits.len() {
+                    8 => channel(&digits[6..8])? as f64 / 255.0,
+                    _ => 1.0,
+                };
+                Self::rgba(
+                    channel(&digits[0..2])?,
+                    channel(&digits[2..4])?,
+                    channel(&digits[4..6])?,
+                    alpha,
+                )
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// One of the named CSS colors, ignoring case.
+    pub fn named(name: &str) -> Result<Self, FigureError> {
+        NAMED_COLORS
+            .iter()
+            .find(|(named, _)| named.eq_ignore_ascii_case(name))
+            .map(|(_, hex)| Self::hex(hex))
+            .unwrap_or_else(|| Err(FigureError::InvalidColor(name.to_string())))
+    }
+
+    /// The color as six hex digits, followed by two more for the alpha unless it is opaque.
+    pub fn hex_string(&self) -> String {
+        with_opacity(
+            &format!("{:02X}{:02X}{:02X}", self.red, self.green, self.blue),
+            self.alpha,
+        )
+    }
 }
 
+/// How opaque a curve or region is, from fully transparent at zero to fully opaque at one.
+///
+/// The constructor checks the value, so an opacity is always valid once built.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Opacity(f64);
+
+impl Opacity {
+    /// An opacity between zero and one.
+    pub fn new(opacity: f64) -> Result<Self, FigureError> {
+        match (0.0..=1.0).contains(&opacity) {
+            true => Ok(Self(opacity)),
+            false => Err(FigureError::InvalidOpacity(opacity)),
+        }
+    }
+
+    /// The opacity as a number between zero and one.
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+/// The opacity of region fills without an explicit opacity, the `AA` alpha of the brand.
+pub const REGION_OPACITY: f64 = 170.0 / 255.0;
+
+/// Scales the alpha of a six or eight digit hex color by `opacity`.
+///
+/// Opaque colors keep six digits, translucent ones get the alpha appended as two more.
+pub(crate) fn with_opacity(color: &str, opacity: f64) -> String {
+    let alpha = color
+        .get(6..8)
+        .and_then(|alpha| u8::from_str_radix(alpha, 16).ok())
+        .map_or(1.0, |alpha| alpha as f64 / 255.0);
+    let alpha = (alpha * opacity).clamp(0.0, 1.0);
+    match alpha >= 1.0 {
+        true => color[..6].to_string(),
+        false => format!("{}{:02X}", &color[..6], (alpha * 255.0).round() as u8),
+    }
+}
+
+#[derive(Clone, Debug)]
 #[allow(unused)]
 pub enum Style {
     Lines(LineEmphasis),
     Markers(MarkerEmphasis),
+    /// A staircase that holds each value until the next x coordinate.
+    Steps(LineEmphasis),
+    /// A smooth spline through the points.
+    Splines(LineEmphasis),
+    /// Markers of the given shape rather than circles.
+    ShapedMarkers(MarkerEmphasis, MarkerShape),
+    /// Straight lines with a marker of the given shape at every point.
+    LinesAndMarkers(LineEmphasis, MarkerEmphasis, MarkerShape),
 }
 
+impl Style {
+    /// The line this style draws, if any, and how it joins the points.
+    pub fn line(&self) -> Option<(&LineEmphasis, LineShape)> {
+        match self {
+            Style::Lines(emphasis) | Style::LinesAndMarkers(emphasis, _, _) => {
+                Some((emphasis, LineShape::Linear))
+            }
+            Style::Steps(emphasis) => Some((emphasis, LineShape::Hv)),
+            Style::Splines(emphasis) => Some((emphasis, LineShape::Spline)),
+            Style::Markers(_) | Style::ShapedMarkers(_, _) => None,
+        }
+    }
+
+    /// The markers this style draws at the points, if any.
+    pub fn marker(&self) -> Option<(&MarkerEmphasis, &MarkerShape)> {
+        match self {
+            Style::Markers(emphasis) => Some((emphasis, &MarkerShape::Circle)),
+            Style::ShapedMarkers(emphasis, shape) | Style::LinesAndMarkers(_, emphasis, shape) => {
+                Some((emphasis, shape))
+            }
+            Style::Lines(_) | Style::Steps(_) | Style::Splines(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[allow(unused)]
 pub enum LineEmphasis {
     Light,
     Heavy,
     Dashed,
+    Dotted,
+    DashDot,
 }
 
+impl LineEmphasis {
+    /// The dash pattern of the line, `None` for a solid line.
+    pub fn dash(&self) -> Option<DashType> {
+        match self {
+            LineEmphasis::Light | LineEmphasis::Heavy => None,
+            LineEmphasis::Dashed => Some(DashType::Dash),
+            LineEmphasis::Dotted => Some(DashType::Dot),
+            LineEmphasis::DashDot => Some(DashType::DashDot),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[allow(unused)]
 pub enum MarkerEmphasis {
     Light,
     Heavy,
 }
 
+#[derive(Clone, Debug, Default)]
+#[allow(unused)]
+pub enum MarkerShape {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+    Cross,
+}
+
+impl MarkerShape {
+    /// The plotly symbol drawn for this shape.
+    pub fn symbol(&self) -> MarkerSymbol {
+        match self {
+            MarkerShape::Circle => MarkerSymbol::Circle,
+            MarkerShape::Square => MarkerSymbol::Square,
+            MarkerShape::Diamond => MarkerSymbol::Diamond,
+            MarkerShape::Cross => MarkerSymbol::Cross,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
 #[allow(unused)]
 pub struct RegionDesign {
     pub color: Color,
     pub color_slot: usize,
+    /// The opacity of the fill, [`REGION_OPACITY`] if not given.
+    pub opacity: Option<Opacity>,
 }
 
+impl RegionDesign {
+    /// A design of the given color with the default opacity, see [`RegionDesign::opacity`].
+    pub fn new(color: Color, color_slot: usize) -> Self {
+        Self {
+            color,
+            color_slot,
+            opacity: None,
+        }
+    }
+
+    /// Sets the opacity of the fill, outlines and markers.
+    pub fn opacity(mut self, opacity: Opacity) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// The translucent fill color in the given theme and display mode.
+    pub fn fill(&self, skin: Skin) -> String {
+        with_opacity(
+            &skin.resolve(&self.color, self.color_slot),
+            self.opacity
+                .map_or(REGION_OPACITY, |opacity| opacity.value()),
+        )
+    }
+
+    /// The color of outlines, bars and markers in the given theme and display mode.
+    pub fn stroke(&self, skin: Skin) -> String {
+        with_opacity(
+            &skin.resolve(&self.color, self.color_slot),
+            self.opacity.map_or(1.0, |opacity| opacity.value()),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
 #[allow(unused)]
 pub struct CurveDesign {
     pub color: Color,
     pub color_slot: usize,
     pub style: Style,
+    /// The opacity of the line or markers, opaque if not given.
+    pub opacity: Option<Opacity>,
+}
+
+impl CurveDesign {
+    /// An opaque design of the given color and style, see [`CurveDesign::opacity`].
+    pub fn new(color: Color, color_slot: usize, style: Style) -> Self {
+        Self {
+            color,
+            color_slot,
+            style,
+            opacity: None,
+        }
+    }
+
+    /// Sets the opacity of the line or markers.
+    pub fn opacity(mut self, opacity: Opacity) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// The color of the line or markers in the given theme and display mode.
+    pub fn stroke(&self, skin: Skin) -> String {
+        with_opacity(
+            &skin.resolve(&self.color, self.color_slot),
+            self.opacity.map_or(1.0, |opacity| opacity.value()),
+        )
+    }
 }
 
 pub const MAIN_COLOR_SLOT: usize = 5;
 
+/// The order a [`ColorCycle`] hands out slots in, starting at the main slot and moving outwards
+/// so early series are the most legible and consecutive slots stay far apart on the ramp.
+pub const SLOT_ORDER: [usize; 10] = [5, 3, 7, 2, 8, 4, 6, 1, 9, 0];
+
+/// An endless, deterministic sequence of distinct branded colors for series built in a loop.
+/// Every hue family is used at one slot before moving on to the next slot in [`SLOT_ORDER`],
+/// and the sequence starts over once every combination has been handed out.
+#[derive(Clone, Debug)]
+pub struct ColorCycle {
+    families: Vec<Color>,
+    position: usize,
+}
+
+impl ColorCycle {
+    /// Cycles through the green, blue, purple and grey ramps.
+    pub fn new() -> Self {
+        Self::of(vec![Color::Green, Color::Blue, Color::Purple, Color::Grey])
+    }
+
+    /// Cycles through the given hue families, the default families if none are given.
+    ///
+    /// Black, white and custom colors are skipped, since they look the same at every slot
+    /// and would repeat long before the ramps run out.
+    pub fn of(families: Vec<Color>) -> Self {
+        let families = families
+            .into_iter()
+            .filter(|family| family.ramp().is_some())
+            .collect::<Vec<Color>>();
+        match families.is_empty() {
+            true => Self::new(),
+            false => Self {
+                families,
+                position: 0,
+            },
+        }
+    }
+
+    /// The color and slot of the `n`-th series, independent of how far the cycle has advanced.
+    pub fn color(&self, n: usize) -> (Color, usize) {
+        let family = self.families[n % self.families.len()];
+        let slot = SLOT_ORDER[(n / self.families.len()) % SLOT_ORDER.len()];
+        (family, slot)
+    }
+}
+
+impl Default for ColorCycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for ColorCycle {
+    type Item = (Color, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let color = self.color(self.position);
+        self.position += 1;
+        Some(color)
+    }
+}
+
 pub const PRIMITIVE_GREENS: [&str; 10] = [
     "E1FDEA", "BCF2CD", "95E8AF", "6CDD90", "45D471", "2BBA58", "1F9143", "136730", "063F1A",
     "001703",
@@ -72,3 +409,273 @@ pub const PRIMITIVE_GREYS: [&str; 10] = [
 
 pub const PRIMITIVE_BLACK: &str = "151718";
 pub const PRIMITIVE_WHITE: &str = "FFFFFF";
+
+/// The named CSS colors accepted by [`CustomColor::named`].
+const NAMED_COLORS: [(&str, &str); 148] = [
+    ("aliceblue", "F0F8FF"),
+    ("antiquewhite", "FAEBD7"),
+    ("aqua", "00FFFF"),
+    ("aquamarine", "7FFFD4"),
+    ("azure", "F0FFFF"),
+    ("beige", "F5F5DC"),
+    ("bisque", "FFE4C4"),
+    ("black", "000000"),
+    ("blanchedalmond", "FFEBCD"),
+    ("blue", "0000FF"),
+    ("blueviolet", "8A2BE2"),
+    ("brown", "A52A2A"),
+    ("burlywood", "DEB887"),
+    ("cadetblue", "5F9EA0"),
+    ("chartreuse", "7FFF00"),
+    ("chocolate", "D2691E"),
+    ("coral", "FF7F50"),
+    ("cornflowerblue", "6495ED"),
+    ("cornsilk", "FFF8DC"),
+    ("crimson", "DC143C"),
+    ("cyan", "00FFFF"),
+    ("darkblue", "00008B"),
+    ("darkcyan", "008B8B"),
+    ("darkgoldenrod", "B8860B"),
+    ("darkgray", "A9A9A9"),
+    ("darkgreen", "006400"),
+    ("darkgrey", "A9A9A9"),
+    ("darkkhaki", "BDB76B"),
+    ("darkmagenta", "8B008B"),
+    ("darkolivegreen", "556B2F"),
+    ("darkorange", "FF8C00"),
+    ("darkorchid", "9932CC"),
+    ("darkred", "8B0000"),
+    ("darksalmon", "E9967A"),
+    ("darkseagreen", "8FBC8F"),
+    ("darkslateblue", "483D8B"),
+    ("darkslategray", "2F4F4F"),
+    ("darkslategrey", "2F4F4F"),
+    ("darkturquoise", "00CED1"),
+    ("darkviolet", "9400D3"),
+    ("deeppink", "FF1493"),
+    ("deepskyblue", "00BFFF"),
+    ("dimgray", "696969"),
+    ("dimgrey", "696969"),
+    ("dodgerblue", "1E90FF"),
+    ("firebrick", "B22222"),
+    ("floralwhite", "FFFAF0"),
+    ("forestgreen", "228B22"),
+    ("fuchsia", "FF00FF"),
+    ("gainsboro", "DCDCDC"),
+    ("ghostwhite", "F8F8FF"),
+    ("gold", "FFD700"),
+    ("goldenrod", "DAA520"),
+    ("gray", "808080"),
+    ("green", "008000"),
+    ("greenyellow", "ADFF2F"),
+    ("grey", "808080"),
+    ("honeydew", "F0FFF0"),
+    ("hotpink", "FF69B4"),
+    ("indianred", "CD5C5C"),
+    ("indigo", "4B0082"),
+    ("ivory", "FFFFF0"),
+    ("khaki", "F0E68C"),
+    ("lavender", "E6E6FA"),
+    ("lavenderblush", "FFF0F5"),
+    ("lawngreen", "7CFC00"),
+    ("lemonchiffon", "FFFACD"),
+    ("lightblue", "ADD8E6"),
+    ("lightcoral", "F08080"),
+    ("lightcyan", "E0FFFF"),
+    ("lightgoldenrodyellow", "FAFAD2"),
+    ("lightgray", "D3D3D3"),
+    ("lightgreen", "90EE90"),
+    ("lightgrey", "D3D3D3"),
+    ("lightpink", "FFB6C1"),
+    ("lightsalmon", "FFA07A"),
+    ("lightseagreen", "20B2AA"),
+    ("lightskyblue", "87CEFA"),
+    ("lightslategray", "778899"),
+    ("lightslategrey", "778899"),
+    ("lightsteelblue", "B0C4DE"),
+    ("lightyellow", "FFFFE0"),
+    ("lime", "00FF00"),
+    ("limegreen", "32CD32"),
+    ("linen", "FAF0E6"),
+    ("magenta", "FF00FF"),
+    ("maroon", "800000"),
+    ("mediumaquamarine", "66CDAA"),
+    ("mediumblue", "0000CD"),
+    ("mediumorchid", "BA55D3"),
+    ("mediumpurple", "9370DB"),
+    ("mediumseagreen", "3CB371"),
+    ("mediumslateblue", "7B68EE"),
+    ("mediumspringgreen", "00FA9A"),
+    ("mediumturquoise", "48D1CC"),
+    ("mediumvioletred", "C71585"),
+    ("midnightblue", "191970"),
+    ("mintcream", "F5FFFA"),
+    ("mistyrose", "FFE4E1"),
+    ("moccasin", "FFE4B5"),
+    ("navajowhite", "FFDEAD"),
+    ("navy", "000080"),
+    ("oldlace", "FDF5E6"),
+    ("olive", "808000"),
+    ("olivedrab", "6B8E23"),
+    ("orange", "FFA500"),
+    ("orangered", "FF4500"),
+    ("orchid", "DA70D6"),
+    ("palegoldenrod", "EEE8AA"),
+    ("palegreen", "98FB98"),
+    ("paleturquoise", "AFEEEE"),
+    ("palevioletred", "DB7093"),
+    ("papayawhip", "FFEFD5"),
+    ("peachpuff", "FFDAB9"),
+    ("peru", "CD853F"),
+    ("pink", "FFC0CB"),
+    ("plum", "DDA0DD"),
+    ("powderblue", "B0E0E6"),
+    ("purple", "800080"),
+    ("rebeccapurple", "663399"),
+    ("red", "FF0000"),
+    ("rosybrown", "BC8F8F"),
+    ("royalblue", "4169E1"),
+    ("saddlebrown", "8B4513"),
+    ("salmon", "FA8072"),
+    ("sandybrown", "F4A460"),
+    ("seagreen", "2E8B57"),
+    ("seashell", "FFF5EE"),
+    ("sienna", "A0522D"),
+    ("silver", "C0C0C0"),
+    ("skyblue", "87CEEB"),
+    ("slateblue", "6A5ACD"),
+    ("slategray", "708090"),
+    ("slategrey", "708090"),
+    ("snow", "FFFAFA"),
+    ("springgreen", "00FF7F"),
+    ("steelblue", "4682B4"),
+    ("tan", "D2B48C"),
+    ("teal", "008080"),
+    ("thistle", "D8BFD8"),
+    ("tomato", "FF6347"),
+    ("turquoise", "40E0D0"),
+    ("violet", "EE82EE"),
+    ("wheat", "F5DEB3"),
+    ("white", "FFFFFF"),
+    ("whitesmoke", "F5F5F5"),
+    ("yellow", "FFFF00"),
+    ("yellowgreen", "9ACD32"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_cycle_skips_families_without_a_ramp() {
+        let black = Color::Custom(CustomColor::rgb(0, 0, 0));
+        let cycle = ColorCycle::of(vec![Color::Black, Color::Blue, black, Color::White]);
+        let colors = cycle.take(10).collect::<Vec<(Color, usize)>>();
+        assert!(colors.iter().all(|(color, _)| matches!(color, Color::Blue)));
+        let slots = colors.iter().map(|(_, slot)| *slot).collect::<Vec<usize>>();
+        assert_eq!(slots, SLOT_ORDER);
+    }
+
+    #[test]
+    fn color_cycle_falls_back_to_the_default_families() {
+        let mut colors = ColorCycle::of(vec![Color::White])
+            .take(40)
+            .map(|color| format!("{:?}", color))
+            .collect::<Vec<String>>();
+        colors.sort();
+        colors.dedup();
+        assert_eq!(colors.len(), 40);
+        assert!(matches!(ColorCycle::of(vec![]).color(1), (Color::Blue, 5)));
+    }
+
+    #[test]
+    fn hex_reads_three_six_and_eight_digits() {
+        assert_eq!(
+            CustomColor::hex("#abc").unwrap(),
+            CustomColor::rgb(0xAA, 0xBB, 0xCC)
+        );
+        assert_eq!(
+            CustomColor::hex("1a2B3c").unwrap(),
+            CustomColor::rgb(0x1A, 0x2B, 0x3C)
+        );
+        let translucent = CustomColor::hex("#1A2B3C80").unwrap();
+        assert_eq!(translucent.hex_string(), "1A2B3C80");
+        assert_eq!(CustomColor::hex("1A2B3CFF").unwrap().hex_string(), "1A2B3C");
+    }
+
+    #[test]
+    fn hex_rejects_other_input() {
+        for hex in [
+            "",
+            "#",
+            "#12",
+            "12345",
+            "1234567",
+            "#ggg",
+            "+1a2b3",
+            "#1A2B3C4D5E",
+            "ééé",
+        ] {
+            assert!(
+                matches!(CustomColor::hex(hex), Err(FigureError::InvalidColor(ref color)) if color == hex),
+                "{} was accepted",
+                hex
+            );
+        }
+    }
+
+    #[test]
+    fn with_opacity_scales_the_alpha() {
+        assert_eq!(with_opacity("1A2B3C", 1.0), "1A2B3C");
+        assert_eq!(with_opacity("1A2B3C", 0.5), "1A2B3C80");
+        assert_eq!(with_opacity("1A2B3C", 0.0), "1A2B3C00");
+        assert_eq!(with_opacity("1A2B3C80", 0.5), "1A2B3C40");
+        assert_eq!(with_opacity("1A2B3C80", 4.0), "1A2B3C");
+    }
+
+    #[test]
+    fn styles_split_into_a_line_and_markers() {
+        use serde_json::{json, to_value, Value};
+        let line = |style: Style| {
+            to_value(
+                style
+                    .line()
+                    .map(|(emphasis, shape)| (emphasis.dash(), shape)),
+            )
+            .unwrap()
+        };
+        let marker =
+            |style: Style| to_value(style.marker().map(|(_, shape)| shape.symbol())).unwrap();
+        assert_eq!(
+            line(Style::Lines(LineEmphasis::Dashed)),
+            json!(["dash", "linear"])
+        );
+        assert_eq!(line(Style::Steps(LineEmphasis::Light)), json!([null, "hv"]));
+        assert_eq!(
+            line(Style::Splines(LineEmphasis::Dotted)),
+            json!(["dot", "spline"])
+        );
+        assert_eq!(line(Style::Markers(MarkerEmphasis::Light)), Value::Null);
+        assert_eq!(
+            marker(Style::Markers(MarkerEmphasis::Light)),
+            json!("circle")
+        );
+        assert_eq!(
+            marker(Style::ShapedMarkers(
+                MarkerEmphasis::Heavy,
+                MarkerShape::Cross
+            )),
+            json!("cross")
+        );
+        let both = || {
+            Style::LinesAndMarkers(
+                LineEmphasis::DashDot,
+                MarkerEmphasis::Light,
+                MarkerShape::Square,
+            )
+        };
+        assert_eq!(line(both()), json!(["dashdot", "linear"]));
+        assert_eq!(marker(both()), json!("square"));
+        assert_eq!(marker(Style::Steps(LineEmphasis::Heavy)), Value::Null);
+    }
+}
@@ -0,0 +1,213 @@
+//! Box and violin plots of samples grouped by category.
+
+#![warn(missing_docs)]
+use plotly::{
+    box_plot::BoxPoints,
+    common::{Line, Marker},
+    BoxPlot, Trace,
+};
+use serde::Serialize;
+
+use crate::{
+    design::*,
+    error::FigureError,
+    plot::{legend_group, legend_name, validate_color_slot, Grouped},
+    theme::Skin,
+};
+
+/// How the samples of each category are summarized.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum DistributionStyle {
+    /// A box from the first to the third quartile with whiskers and outliers.
+    #[default]
+    Box,
+    /// A mirrored kernel density estimate with the quartile box drawn inside.
+    Violin,
+}
+
+/// A struct to hold samples grouped by category, drawn as boxes or violins.
+pub struct Distributions {
+    /// The category and samples of each box or violin, drawn in order.
+    pub samples: Vec<(String, Vec<f64>)>,
+    /// Whether the samples are drawn as boxes or violins.
+    pub style: DistributionStyle,
+    /// The color of the boxes or violins.
+    pub design: RegionDesign,
+    /// An optional name for the series that appears in the legend.
+    pub name: Option<String>,
+    /// An optional legend group, so the boxes or violins toggle with the samples they summarize.
+    pub legend_group: Option<String>,
+}
+
+/// Whether a part of a violin is drawn.
+#[derive(Serialize, Clone)]
+struct Visible {
+    visible: bool,
+}
+
+/// A violin trace, which plotly supports but this version of the plotly crate does not.
+#[derive(Serialize, Clone)]
+struct Violin {
+    r#type: &'static str,
+    x: Vec<String>,
+    y: Vec<f64>,
+    name: String,
+    #[serde(rename = "showlegend")]
+    show_legend: bool,
+    #[serde(rename = "legendgroup", skip_serializing_if = "Option::is_none")]
+    legend_group: Option<String>,
+    #[serde(rename = "fillcolor")]
+    fill_color: String,
+    line: Line,
+    #[serde(rename = "box")]
+    quartiles: Visible,
+    #[serde(rename = "meanline")]
+    mean_line: Visible,
+    points: bool,
+    #[serde(rename = "spanmode")]
+    span_mode: &'static str,
+    #[serde(rename = "xaxis")]
+    x_axis: String,
+    #[serde(rename = "yaxis")]
+    y_axis: String,
+}
+
+impl Trace for Violin {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+impl Distributions {
+    /// Checks that the color exists.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        validate_color_slot(&self.design.color, self.design.color_slot)
+    }
+
+    /// Every sample, for fitting the value axis.
+    pub(crate) fn values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.samples
+            .iter()
+            .flat_map(|(_, samples)| samples.iter().cloned())
+    }
+
+    /// Builds the box or violin trace for this series on the given axes.
+    pub(crate) fn trace(&self, skin: Skin, x_axis: &str, y_axis: &str) -> Box<dyn Trace> {
+        let (categories, values): (Vec<String>, Vec<f64>) = self
+            .samples
+            .iter()
+            .flat_map(|(category, samples)| {
+                samples
+                    .iter()
+                    .map(move |sample| (category.clone(), *sample))
+            })
+            .unzip();
+        let line = Line::new()
+            .color(skin.foreground())
+            .width(skin.theme.lines.light);
+        match self.style {
+            DistributionStyle::Box => {
+                let trace = BoxPlot::new_xy(categories, values)
+                    .fill_color(self.design.fill(skin))
+                    .line(line)
+                    .marker(Marker::new().color(self.design.stroke(skin)))
+                    .box_points(BoxPoints::Outliers)
+                    .name(legend_name(&self.name, skin))
+                    .show_legend(self.name.is_some())
+                    .x_axis(x_axis)
+                    .y_axis(y_axis);
+                trace.grouped(&self.legend_group, &self.name, skin)
+            }
+            DistributionStyle::Violin => Box::new(Violin {
+                r#type: "violin",
+                x: categories,
+                y: values,
+                name: legend_name(&self.name, skin),
+                show_legend: self.name.is_some(),
+                legend_group: legend_group(&self.legend_group, &self.name, skin).map(String::from),
+                fill_color: self.design.fill(skin),
+                line,
+                quartiles: Visible { visible: true },
+                mean_line: Visible { visible: true },
+                points: false,
+                span_mode: "hard",
+                x_axis: x_axis.to_string(),
+                y_axis: y_axis.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        figure::Figure,
+        theme::{TextMode, Theme},
+    };
+
+    fn distributions(style: DistributionStyle) -> Distributions {
+        Distributions {
+            samples: vec![
+                (String::from("low"), vec![1.0, 2.0]),
+                (String::from("high"), vec![3.0]),
+            ],
+            style,
+            design: RegionDesign::new(Color::Purple, MAIN_COLOR_SLOT),
+            name: Some(String::from("outcomes")),
+            legend_group: Some(String::from("ensemble")),
+        }
+    }
+
+    fn trace_json(distributions: &Distributions) -> serde_json::Value {
+        let theme = Theme::default();
+        let skin = Skin {
+            theme: &theme,
+            mode: DisplayMode::Dark,
+            text: TextMode::Raw,
+            grouped_legend: false,
+        };
+        serde_json::from_str(&distributions.trace(skin, "x3", "y3").to_json()).unwrap()
+    }
+
+    #[test]
+    fn violins_serialize_as_plotly_violins() {
+        let json = trace_json(&distributions(DistributionStyle::Violin));
+        assert_eq!(json["type"], "violin");
+        assert_eq!(json["x"], serde_json::json!(["low", "low", "high"]));
+        assert_eq!(json["y"], serde_json::json!([1.0, 2.0, 3.0]));
+        assert_eq!(json["box"]["visible"], true);
+        assert_eq!(json["meanline"]["visible"], true);
+        assert_eq!(json["legendgroup"], "ensemble");
+        assert_eq!(json["showlegend"], true);
+        assert_eq!(json["name"], " outcomes");
+        assert_eq!(
+            (&json["xaxis"], &json["yaxis"]),
+            (&"x3".into(), &"y3".into())
+        );
+        let ungrouped = Distributions {
+            legend_group: None,
+            ..distributions(DistributionStyle::Violin)
+        };
+        assert!(trace_json(&ungrouped).get("legendgroup").is_none());
+    }
+
+    #[test]
+    fn boxes_go_to_a_category_x_axis() {
+        let json = trace_json(&distributions(DistributionStyle::Box));
+        assert_eq!(json["type"], "box");
+        assert_eq!(json["legendgroup"], "ensemble");
+        assert_eq!(
+            (&json["xaxis"], &json["yaxis"]),
+            (&"x3".into(), &"y3".into())
+        );
+        let plot = Figure::new()
+            .distributions(distributions(DistributionStyle::Box))
+            .build()
+            .ok()
+            .unwrap();
+        let layout = serde_json::to_value(plot).unwrap()["layout"].clone();
+        assert_eq!(layout["xaxis"]["type"], "category");
+        assert_eq!(layout["yaxis"]["type"], "linear");
+    }
+}
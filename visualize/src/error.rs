@@ -0,0 +1,173 @@
+//! Errors raised while building or writing a figure.
+
+#![warn(missing_docs)]
+use std::{fmt, io, path::PathBuf};
+
+/// The ways building or writing a figure can fail.
+#[derive(Debug)]
+pub enum FigureError {
+    /// A curve or region has a different number of x and y coordinates.
+    MismatchedCoordinates {
+        /// The legend name of the offending trace, if it has one.
+        name: Option<String>,
+        /// The number of x coordinates.
+        x_len: usize,
+        /// The number of y coordinates.
+        y_len: usize,
+    },
+    /// A trace has a different number of errors than values.
+    MismatchedErrors {
+        /// The legend name of the offending trace, if it has one.
+        name: Option<String>,
+        /// The number of values.
+        values: usize,
+        /// The number of errors.
+        errors: usize,
+    },
+    /// A surface has a row of z values that does not match its x and y coordinates.
+    MismatchedSurface {
+        /// The number of x coordinates, which every row must match.
+        x_len: usize,
+        /// The number of y coordinates, which the number of rows must match.
+        y_len: usize,
+        /// The number of rows of z values.
+        rows: usize,
+        /// The length of the first row that does not match, or of the first row otherwise.
+        columns: usize,
+    },
+    /// A log axis has a bound that is not positive.
+    LogBound(f64),
+    /// A histogram asks for zero bins, for bins without a positive width,
+    /// or for more bins than [`crate::histogram::MAX_BINS`].
+    InvalidBins,
+    /// A color slot falls outside of the ten step Primitive ramps.
+    ColorSlot(usize),
+    /// A custom color is not a valid hex string or CSS color name.
+    InvalidColor(String),
+    /// An opacity or alpha falls outside of zero to one.
+    InvalidOpacity(f64),
+    /// A theme file could not be parsed.
+    Theme(String),
+    /// A figure size has a width or height of zero.
+    InvalidSize {
+        /// The requested width in pixels.
+        width: usize,
+        /// The requested height in pixels.
+        height: usize,
+    },
+    /// The figure has more panels than its grid has cells.
+    GridTooSmall {
+        /// The number of panels in the figure.
+        panels: usize,
+        /// The number of cells in the grid.
+        cells: usize,
+    },
+    /// The grid needs more axes than plotly supports.
+    TooManyAxes(usize),
+    /// The output file already exists and overwriting was not requested.
+    FileExists(PathBuf),
+    /// Writing the output file failed.
+    Io(io::Error),
+    /// An image export scale is not a positive number.
+    InvalidScale(f64),
+    /// The kaleido binary failed to export a static image.
+    Export {
+        /// The file the image was meant for.
+        path: PathBuf,
+        /// What kaleido reported.
+        reason: String,
+    },
+}
+
+impl fmt::Display for FigureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FigureError::MismatchedCoordinates { name, x_len, y_len } => write!(
+                f,
+                "trace {} has {} x coordinates but {} y coordinates",
+                name.as_deref().unwrap_or("<unnamed>"),
+                x_len,
+                y_len
+            ),
+            FigureError::MismatchedErrors {
+                name,
+                values,
+                errors,
+            } => write!(
+                f,
+                "trace {} has {} values but {} errors",
+                name.as_deref().unwrap_or("<unnamed>"),
+                values,
+                errors
+            ),
+            FigureError::MismatchedSurface {
+                x_len,
+                y_len,
+                rows,
+                columns,
+            } => write!(
+                f,
+                "surface over {} x and {} y coordinates has {} by {} z values",
+                x_len, y_len, rows, columns
+            ),
+            FigureError::LogBound(bound) => {
+                write!(f, "log axis bound {} is not positive", bound)
+            }
+            FigureError::InvalidBins => {
+                write!(
+                    f,
+                    "histogram bins need a positive count or width and at most {} bins",
+                    crate::histogram::MAX_BINS
+                )
+            }
+            FigureError::ColorSlot(slot) => {
+                write!(f, "color slot {} is outside of the palette ramp", slot)
+            }
+            FigureError::InvalidColor(color) => {
+                write!(f, "{} is not a hex color or CSS color name", color)
+            }
+            FigureError::InvalidOpacity(opacity) => {
+                write!(f, "opacity {} is outside of 0 to 1", opacity)
+            }
+            FigureError::Theme(error) => write!(f, "invalid theme: {}", error),
+            FigureError::InvalidSize { width, height } => {
+                write!(f, "figure size {} by {} is not positive", width, height)
+            }
+            FigureError::GridTooSmall { panels, cells } => {
+                write!(
+                    f,
+                    "{} panels do not fit in a grid of {} cells",
+                    panels, cells
+                )
+            }
+            FigureError::TooManyAxes(count) => {
+                write!(f, "{} axes requested but plotly supports at most 8", count)
+            }
+            FigureError::FileExists(path) => {
+                write!(f, "refusing to overwrite {}", path.display())
+            }
+            FigureError::Io(error) => write!(f, "failed to write figure: {}", error),
+            FigureError::InvalidScale(scale) => {
+                write!(f, "image export scale {} is not positive", scale)
+            }
+            FigureError::Export { path, reason } => {
+                write!(f, "failed to export {}: {}", path.display(), reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FigureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FigureError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FigureError {
+    fn from(error: io::Error) -> Self {
+        FigureError::Io(error)
+    }
+}
@@ -0,0 +1,123 @@
+//! Fan charts summarizing an ensemble of paths by their quantiles at every step.
+
+#![warn(missing_docs)]
+use crate::{
+    design::*,
+    functions::quantile,
+    plot::{Curve, Region},
+};
+
+/// The quantiles bounding each band of a fan chart, from the outermost band inwards.
+pub const FAN_BANDS: [(f64, f64); 2] = [(0.05, 0.95), (0.25, 0.75)];
+
+/// The slots of the bands in [`FAN_BANDS`], dimmer for the outer bands.
+const BAND_SLOTS: [usize; 2] = [7, 5];
+
+/// The slot of the median curve, brighter than every band.
+const MEDIAN_SLOT: usize = 2;
+
+/// A struct to hold an ensemble of paths summarized as nested quantile bands around the median.
+pub struct FanChart {
+    /// The x coordinate of every step of the paths.
+    pub x_coordinates: Vec<f64>,
+    /// The paths, one y coordinate per x coordinate.
+    pub paths: Vec<Vec<f64>>,
+    /// The hue of the bands and the median.
+    pub color: Color,
+    /// An optional name for the median that appears in the legend.
+    pub name: Option<String>,
+}
+
+/// The `q`-th quantile across paths at every step.
+///
+/// Steps past the end of a path are summarized over the paths that still have a value,
+/// and the quantiles stop at the end of the longest path.
+/// NaN values are skipped, and a step where every path is NaN gets a NaN quantile.
+pub fn path_quantiles(paths: &[Vec<f64>], steps: usize, q: f64) -> Vec<f64> {
+    let longest = paths.iter().map(Vec::len).max().unwrap_or(0).min(steps);
+    (0..longest)
+        .map(|step| {
+            let mut values = paths
+                .iter()
+                .filter_map(|path| path.get(step).cloned())
+                .filter(|value| !value.is_nan())
+                .collect::<Vec<f64>>();
+            if values.is_empty() {
+                return f64::NAN;
+            }
+            values.sort_by(f64::total_cmp);
+            quantile(&values, q)
+        })
+        .collect()
+}
+
+impl FanChart {
+    /// The x coordinates of the steps the paths have values at.
+    fn x_coordinates(&self, steps: usize) -> Vec<f64> {
+        self.x_coordinates[..steps].to_vec()
+    }
+
+    /// The nested bands between the quantiles in [`FAN_BANDS`], outermost first.
+    pub fn regions(&self) -> Vec<Region> {
+        FAN_BANDS
+            .iter()
+            .zip(BAND_SLOTS)
+            .map(|((lower, upper), color_slot)| {
+                let lower = path_quantiles(&self.paths, self.x_coordinates.len(), *lower);
+                let upper = path_quantiles(&self.paths, self.x_coordinates.len(), *upper);
+                let x_coordinates = self.x_coordinates(lower.len());
+                Region {
+                    x_coordinates: (x_coordinates.clone(), x_coordinates),
+                    y_coordinates: (lower, upper),
+                    design: RegionDesign::new(self.color, color_slot),
+                    name: None,
+                    legend_group: None,
+                }
+            })
+            .collect()
+    }
+
+    /// The median path.
+    pub fn median(&self) -> Curve {
+        let y_coordinates = path_quantiles(&self.paths, self.x_coordinates.len(), 0.5);
+        let design = CurveDesign::new(self.color, MEDIAN_SLOT, Style::Lines(LineEmphasis::Heavy));
+        Curve {
+            name: self.name.clone(),
+            ..Curve::new(
+                self.x_coordinates(y_coordinates.len()),
+                y_coordinates,
+                design,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantiles_interpolate_between_paths() {
+        let paths = vec![vec![0.0, 10.0], vec![1.0, 20.0], vec![3.0, 40.0]];
+        assert_eq!(path_quantiles(&paths, 2, 0.5), vec![1.0, 20.0]);
+        assert_eq!(path_quantiles(&paths, 2, 0.25), vec![0.5, 15.0]);
+        assert_eq!(path_quantiles(&paths, 1, 1.0), vec![3.0]);
+    }
+
+    #[test]
+    fn quantiles_use_the_paths_that_are_still_running() {
+        let paths = vec![vec![1.0, 2.0, 3.0], vec![5.0]];
+        assert_eq!(path_quantiles(&paths, 5, 0.5), vec![3.0, 2.0, 3.0]);
+        assert!(path_quantiles(&[], 5, 0.5).is_empty());
+    }
+
+    #[test]
+    fn quantiles_skip_nan() {
+        let paths = vec![vec![f64::NAN, f64::NAN, 4.0], vec![2.0, f64::NAN, 6.0]];
+        let medians = path_quantiles(&paths, 3, 0.5);
+        assert_eq!(medians.len(), 3);
+        assert_eq!(medians[0], 2.0);
+        assert!(medians[1].is_nan());
+        assert_eq!(medians[2], 5.0);
+    }
+}
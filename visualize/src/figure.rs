@@ -0,0 +1,1676 @@
+//! A builder for branded figures made of one or more panels of curves and regions.
+
+#![warn(missing_docs)]
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use plotly::{
+    common::{Anchor, AxisSide, ExponentFormat, Font, Orientation, TickMode, Title},
+    layout::{Axis, AxisType, Legend, Margin, RangeSlider, TraceOrder},
+    Layout, Plot,
+};
+use plotly_kaleido::Kaleido;
+
+use crate::{
+    annotations::Annotation,
+    bars::{value_extents, BarMode, BarOrientation, Bars},
+    candles::Candlesticks,
+    colormap::CurveFamily,
+    design::*,
+    distributions::Distributions,
+    error::FigureError,
+    fan::FanChart,
+    histogram::Histogram,
+    plot::{Axes, Curve, Display, ImageExport, Region, Scale, SecondaryAxis, Surface2D},
+    theme::{FigureSize, Skin, TextMode, Theme},
+};
+
+/// The fraction of the figure left blank between neighbouring panels.
+const PANEL_GAP: f64 = 0.08;
+
+/// Plotly only supports this many x and y axes in a layout.
+const MAX_AXES: usize = 8;
+
+/// The horizontal distance between neighbouring colorbars, as a fraction of the figure.
+const COLOR_BAR_GAP: f64 = 0.1;
+
+/// The distance between a legend inside the plot and its corner, as a fraction of the plot.
+const LEGEND_INSET: f64 = 0.02;
+
+/// A single set of axes and everything drawn on them.
+#[derive(Default)]
+pub struct Panel {
+    /// The curves drawn on top of the regions.
+    curves: Vec<Curve>,
+    /// The curves drawn against the secondary right-hand y-axis.
+    secondary_curves: Vec<Curve>,
+    /// The filled regions drawn underneath the curves.
+    regions: Vec<Region>,
+    /// The curves colored by a parameter, each family with its own colorbar.
+    families: Vec<CurveFamily>,
+    /// The heatmaps drawn underneath everything else, each with its own colorbar.
+    surfaces: Vec<Surface2D>,
+    /// The histograms drawn over the regions and under the curves.
+    histograms: Vec<Histogram>,
+    /// The series of bars drawn over the histograms and under the curves.
+    bars: Vec<Bars>,
+    /// The candlestick charts drawn over the bars and under the curves.
+    candlesticks: Vec<Candlesticks>,
+    /// The boxes and violins drawn over the candlesticks and under the curves.
+    distributions: Vec<Distributions>,
+    /// The labels, reference lines, spans and points drawn over everything else.
+    annotations: Vec<Annotation>,
+    /// The labels and limits of the axes.
+    axes: Axes,
+}
+
+impl Panel {
+    /// Creates an empty panel.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a curve to the panel.
+    pub fn curve(mut self, curve: Curve) -> Self {
+        self.curves.push(curve);
+        self
+    }
+
+    /// Adds several curves to the panel.
+    pub fn curves(mut self, curves: Vec<Curve>) -> Self {
+        self.curves.extend(curves);
+        self
+    }
+
+    /// Adds a curve drawn against the secondary right-hand y-axis.
+    pub fn secondary_curve(mut self, curve: Curve) -> Self {
+        self.secondary_curves.push(curve);
+        self
+    }
+
+    /// Adds curves colored by a parameter along with their colorbar.
+    pub fn curve_family(mut self, family: CurveFamily) -> Self {
+        self.families.push(family);
+        self
+    }
+
+    /// Adds a heatmap, with optional contour lines, to the panel.
+    pub fn surface(mut self, surface: Surface2D) -> Self {
+        self.surfaces.push(surface);
+        self
+    }
+
+    /// Adds a histogram, with its optional density overlay, to the panel.
+    pub fn histogram(mut self, histogram: Histogram) -> Self {
+        self.histograms.push(histogram);
+        self
+    }
+
+    /// Adds a series of bars to the panel.
+    pub fn bars(mut self, bars: Bars) -> Self {
+        self.bars.push(bars);
+        self
+    }
+
+    /// Adds a candlestick chart to the panel.
+    pub fn candlesticks(mut self, candlesticks: Candlesticks) -> Self {
+        self.candlesticks.push(candlesticks);
+        self
+    }
+
+    /// Adds boxes or violins of samples grouped by category to the panel.
+    pub fn distributions(mut self, distributions: Distributions) -> Self {
+        self.distributions.push(distributions);
+        self
+    }
+
+    /// Adds the quantile bands and median of an ensemble of paths to the panel.
+    pub fn fan_chart(self, fan_chart: FanChart) -> Self {
+        self.regions(fan_chart.regions()).curve(fan_chart.median())
+    }
+
+    /// Adds a label, reference line, span or point to the panel.
+    pub fn annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    /// Adds several annotations to the panel.
+    pub fn annotations(mut self, annotations: Vec<Annotation>) -> Self {
+        self.annotations.extend(annotations);
+        self
+    }
+
+    /// Adds a filled region to the panel.
+    pub fn region(mut self, region: Region) -> Self {
+        self.regions.push(region);
+        self
+    }
+
+    /// Adds several filled regions to the panel.
+    pub fn regions(mut self, regions: Vec<Region>) -> Self {
+        self.regions.extend(regions);
+        self
+    }
+
+    /// Sets the axes labels and limits.
+    pub fn axes(mut self, axes: Axes) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Checks every trace in the panel.
+    fn validate(&self) -> Result<(), FigureError> {
+        for region in self.regions.iter() {
+            region.validate()?;
+        }
+        for curve in self.curves.iter().chain(self.secondary_curves.iter()) {
+            curve.validate()?;
+        }
+        for family in self.families.iter() {
+            family.validate()?;
+        }
+        for surface in self.surfaces.iter() {
+            surface.validate()?;
+        }
+        for histogram in self.histograms.iter() {
+            histogram.validate()?;
+        }
+        for bars in self.bars.iter() {
+            bars.validate()?;
+        }
+        for candlesticks in self.candlesticks.iter() {
+            candlesticks.validate()?;
+        }
+        for distributions in self.distributions.iter() {
+            distributions.validate()?;
+        }
+        for annotation in self.annotations.iter() {
+            annotation.validate()?;
+        }
+        self.axes.validate()
+    }
+
+    /// Every x coordinate drawn in the panel, including the values of horizontal bars.
+    fn x_values(&self, bar_mode: BarMode) -> impl Iterator<Item = f64> + '_ {
+        let regions = self
+            .regions
+            .iter()
+            .flat_map(|region| region.x_coordinates.0.iter().chain(&region.x_coordinates.1));
+        let curves = self
+            .curves
+            .iter()
+            .chain(self.secondary_curves.iter())
+            .chain(self.family_curves())
+            .flat_map(Curve::x_values);
+        let surfaces = self
+            .surfaces
+            .iter()
+            .flat_map(|surface| surface.x_coordinates.iter())
+            .chain(
+                self.candlesticks
+                    .iter()
+                    .flat_map(|candlesticks| candlesticks.x_coordinates.iter()),
+            );
+        let histograms = self.histograms.iter().flat_map(|histogram| {
+            let density = histogram.density_curve().map(|curve| curve.x_coordinates);
+            histogram
+                .outline()
+                .0
+                .into_iter()
+                .chain(density.into_iter().flatten())
+        });
+        let bars = value_extents(&self.bars, bar_mode, BarOrientation::Horizontal);
+        let annotations = self.annotations.iter().flat_map(Annotation::x_values);
+        regions
+            .chain(surfaces)
+            .cloned()
+            .chain(curves)
+            .chain(histograms)
+            .chain(bars)
+            .chain(annotations)
+    }
+
+    /// Every y coordinate drawn against the primary y-axis of the panel,
+    /// including the values of vertical bars.
+    fn y_values(&self, bar_mode: BarMode) -> impl Iterator<Item = f64> + '_ {
+        let regions = self
+            .regions
+            .iter()
+            .flat_map(|region| region.y_coordinates.0.iter().chain(&region.y_coordinates.1));
+        let curves = self
+            .curves
+            .iter()
+            .chain(self.family_curves())
+            .flat_map(Curve::y_values);
+        let surfaces = self
+            .surfaces
+            .iter()
+            .flat_map(|surface| surface.y_coordinates.iter());
+        let histograms = self.histograms.iter().flat_map(|histogram| {
+            let density = histogram.density_curve().map(|curve| curve.y_coordinates);
+            histogram
+                .outline()
+                .1
+                .into_iter()
+                .chain(density.into_iter().flatten())
+        });
+        let bars = value_extents(&self.bars, bar_mode, BarOrientation::Vertical);
+        let candlesticks = self
+            .candlesticks
+            .iter()
+            .flat_map(|candlesticks| candlesticks.y_values());
+        let distributions = self
+            .distributions
+            .iter()
+            .flat_map(|distributions| distributions.values());
+        let annotations = self.annotations.iter().flat_map(Annotation::y_values);
+        regions
+            .chain(surfaces)
+            .cloned()
+            .chain(curves)
+            .chain(histograms)
+            .chain(bars)
+            .chain(candlesticks)
+            .chain(distributions)
+            .chain(annotations)
+    }
+
+    /// Every y coordinate drawn against the secondary y-axis of the panel.
+    fn secondary_y_values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.secondary_curves.iter().flat_map(Curve::y_values)
+    }
+
+    /// Every curve of every family in the panel.
+    fn family_curves(&self) -> impl Iterator<Item = &Curve> + '_ {
+        self.families
+            .iter()
+            .flat_map(|family| family.curves.iter().map(|(_, curve)| curve))
+    }
+
+    /// The number of colorbars drawn for the panel.
+    fn color_bars(&self) -> usize {
+        self.families.len() + self.surfaces.len()
+    }
+
+    /// The x and y scales, where a linear axis that bars, boxes or violins are
+    /// grouped along becomes a category axis so plotly does not read the categories as numbers.
+    fn scales(&self) -> (Scale, Scale) {
+        let (x, y) = self.axes.scales;
+        let horizontal = |bars: &Bars| matches!(bars.orientation, BarOrientation::Horizontal);
+        let x_categories =
+            !self.distributions.is_empty() || self.bars.iter().any(|bars| !horizontal(bars));
+        let y_categories = self.bars.iter().any(horizontal);
+        let categorical = |scale: Scale, categories: bool| match (scale, categories) {
+            (Scale::Linear, true) => Scale::Category,
+            _ => scale,
+        };
+        (categorical(x, x_categories), categorical(y, y_categories))
+    }
+
+    /// Whether the panel needs a secondary right-hand y-axis.
+    fn has_secondary_axis(&self) -> bool {
+        !self.secondary_curves.is_empty() || self.axes.secondary_y.is_some()
+    }
+}
+
+/// Which axes neighbouring panels have in common.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum SharedAxes {
+    /// Every panel has its own x and y axes.
+    #[default]
+    Independent,
+    /// Panels in the same column share one x-axis, labeled below the bottom panel.
+    X,
+    /// Panels in the same row share one y-axis, labeled left of the first panel.
+    Y,
+    /// Panels share x-axes by column and y-axes by row.
+    Both,
+}
+
+/// How the panels of a figure are arranged.
+#[derive(Copy, Clone, Debug)]
+pub struct Grid {
+    /// The number of rows, filled from the top.
+    pub rows: usize,
+    /// The number of columns, filled from the left.
+    pub columns: usize,
+    /// Which axes are shared between panels.
+    pub shared: SharedAxes,
+}
+
+/// Where the legend is placed.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum LegendPosition {
+    /// Wherever plotly finds room, which is the top right of the plot unless that is taken.
+    #[default]
+    Auto,
+    /// Inside the plot in its top left corner.
+    TopLeft,
+    /// Inside the plot in its top right corner.
+    TopRight,
+    /// Inside the plot in its bottom left corner.
+    BottomLeft,
+    /// Inside the plot in its bottom right corner.
+    BottomRight,
+    /// Right of the plot, past any secondary axis and colorbars.
+    OutsideRight,
+    /// Below the plot, under the title of the x-axis.
+    OutsideBottom,
+}
+
+/// Which way the entries of the legend are laid out.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum LegendOrientation {
+    /// One entry below the other.
+    #[default]
+    Vertical,
+    /// One entry next to the other, wrapping when out of room.
+    Horizontal,
+}
+
+/// The placement and contents of the legend.
+#[derive(Clone, Debug, Default)]
+pub struct LegendLayout {
+    /// Where the legend is placed.
+    pub position: LegendPosition,
+    /// Which way its entries are laid out.
+    pub orientation: LegendOrientation,
+    /// Whether traces sharing a legend name, along with the error bands of curves,
+    /// are listed together and shown or hidden with a single click.
+    /// Traces with an explicit legend group are shown or hidden together either way.
+    pub grouped: bool,
+    /// Whether the legend is left out altogether.
+    pub hidden: bool,
+    /// An optional title above the entries.
+    pub title: Option<String>,
+}
+
+/// The axes a panel is drawn on, numbered from one as plotly does.
+struct PanelAxes {
+    x: usize,
+    y: usize,
+    secondary_y: Option<usize>,
+    row: usize,
+    column: usize,
+}
+
+/// Formats a plotly axis id such as `x`, `x2` or `y3`.
+fn axis_id(prefix: &str, index: usize) -> String {
+    match index {
+        1 => prefix.to_string(),
+        _ => format!("{}{}", prefix, index),
+    }
+}
+
+/// The span of the `index`-th of `count` evenly sized slots, leaving a gap between slots.
+fn domain(index: usize, count: usize) -> [f64; 2] {
+    let size = (1.0 - PANEL_GAP * (count - 1) as f64) / count as f64;
+    let start = index as f64 * (size + PANEL_GAP);
+    [start, start + size]
+}
+
+/// Places an axis as the `id`-th x-axis of the layout.
+fn set_x_axis(layout: Layout, id: usize, axis: Axis) -> Layout {
+    match id {
+        1 => layout.x_axis(axis),
+        2 => layout.x_axis2(axis),
+        3 => layout.x_axis3(axis),
+        4 => layout.x_axis4(axis),
+        5 => layout.x_axis5(axis),
+        6 => layout.x_axis6(axis),
+        7 => layout.x_axis7(axis),
+        _ => layout.x_axis8(axis),
+    }
+}
+
+/// Places an axis as the `id`-th y-axis of the layout.
+fn set_y_axis(layout: Layout, id: usize, axis: Axis) -> Layout {
+    match id {
+        1 => layout.y_axis(axis),
+        2 => layout.y_axis2(axis),
+        3 => layout.y_axis3(axis),
+        4 => layout.y_axis4(axis),
+        5 => layout.y_axis5(axis),
+        6 => layout.y_axis6(axis),
+        7 => layout.y_axis7(axis),
+        _ => layout.y_axis8(axis),
+    }
+}
+
+/// Applies the scale type, limits and tick labels in the text mode to an axis.
+///
+/// Log axes with bounds holding two or more powers of ten get a tick at each one labeled as a
+/// power of ten, narrower log axes keep plotly's own ticks,
+/// LaTeX date and category ticks are wrapped in `\\text{}` so their words keep their spacing.
+fn scaled(axis: Axis, scale: Scale, bounds: &[f64], skin: Skin) -> Axis {
+    let size = &skin.theme.latex.ticks;
+    let axis = match (scale, skin.latex_ticks()) {
+        (_, false) => axis,
+        (Scale::Linear | Scale::Log, true) => {
+            axis.tick_prefix(format!("$\\{}{{", size)).tick_suffix("}$")
+        }
+        (Scale::Date | Scale::Category, true) => axis
+            .tick_prefix(format!("$\\{}{{\\text{{", size))
+            .tick_suffix("}}$"),
+    };
+    let axis = match scale {
+        Scale::Linear => axis.type_(AxisType::Linear),
+        Scale::Log => axis.type_(AxisType::Log),
+        Scale::Date => axis.type_(AxisType::Date),
+        Scale::Category => axis.type_(AxisType::Category),
+    };
+    match (scale, bounds.is_empty()) {
+        (_, true) => axis,
+        (Scale::Log, false) => {
+            let exponents = bounds
+                .iter()
+                .map(|bound| bound.log10())
+                .collect::<Vec<f64>>();
+            let lowest = exponents
+                .iter()
+                .cloned()
+                .fold(f64::INFINITY, f64::min)
+                .ceil() as i32;
+            let highest = exponents
+                .iter()
+                .cloned()
+                .fold(f64::NEG_INFINITY, f64::max)
+                .floor() as i32;
+            let axis = axis.range(exponents);
+            // Within a single decade plotly's own ticks are the only ones visible.
+            if highest <= lowest {
+                return axis;
+            }
+            axis.tick_mode(TickMode::Array)
+                .tick_values((lowest..=highest).map(|k| 10_f64.powi(k)).collect())
+                .tick_text(
+                    (lowest..=highest)
+                        .map(|k| match skin.latex_ticks() {
+                            // Plotly skips the tick prefix and suffix for tick text.
+                            true => format!("$\\{}{{10^{{{}}}}}$", size, k),
+                            false => format!("10<sup>{}</sup>", k),
+                        })
+                        .collect(),
+                )
+                .exponent_format(ExponentFormat::None)
+        }
+        (_, false) => axis.range(bounds.to_vec()),
+    }
+}
+
+/// A figure assembled one piece at a time.
+///
+/// Curves, regions and axes given to the figure itself go to the most recently added panel,
+/// so a single panel figure never has to mention panels at all.
+///
+/// ```ignore
+/// Figure::new()
+///     .curve(curve)
+///     .region(region)
+///     .axes(axes)
+///     .title("\\text{Example}")
+///     .display(display)
+///     .save_as("example.html")
+///     .plot();
+///
+/// Figure::new()
+///     .panel(Panel::new().curve(price).axes(price_axes))
+///     .panel(Panel::new().curve(liquidity).axes(liquidity_axes))
+///     .grid(Grid { rows: 2, columns: 1, shared: SharedAxes::X })
+///     .plot();
+/// ```
+#[derive(Default)]
+pub struct Figure {
+    /// The panels of the figure in row-major order.
+    panels: Vec<Panel>,
+    /// The arrangement of the panels, a single column when not given.
+    grid: Option<Grid>,
+    /// The title shown above the plot.
+    title: String,
+    /// The high level visualization settings.
+    display: Display,
+    /// An optional file name to write the plot to.
+    file_name: Option<String>,
+    /// Whether [`Figure::write_html`] may replace an existing file.
+    overwrite: bool,
+    /// An optional static image export used by [`Figure::plot`] instead of html.
+    export: Option<ImageExport>,
+    /// How bars of different series sharing a category are laid out.
+    bar_mode: BarMode,
+    /// The palette, font sizes, line widths and layout of the figure.
+    theme: Theme,
+    /// The size the theme is scaled to when rendering, the size of the theme otherwise.
+    size: Option<FigureSize>,
+    /// Whether text is rendered as LaTeX, as given, or depending on its content.
+    text_mode: TextMode,
+    /// The placement and contents of the legend.
+    legend: LegendLayout,
+}
+
+impl Figure {
+    /// Creates an empty figure with the default display.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a curve to the current panel.
+    pub fn curve(mut self, curve: Curve) -> Self {
+        self.current_panel().curves.push(curve);
+        self
+    }
+
+    /// Adds several curves to the current panel.
+    pub fn curves(mut self, curves: Vec<Curve>) -> Self {
+        self.current_panel().curves.extend(curves);
+        self
+    }
+
+    /// Adds a curve drawn against the secondary right-hand y-axis of the current panel.
+    pub fn secondary_curve(mut self, curve: Curve) -> Self {
+        self.current_panel().secondary_curves.push(curve);
+        self
+    }
+
+    /// Adds curves colored by a parameter to the current panel, along with their colorbar.
+    pub fn curve_family(mut self, family: CurveFamily) -> Self {
+        self.current_panel().families.push(family);
+        self
+    }
+
+    /// Adds a heatmap, with optional contour lines, to the current panel.
+    pub fn surface(mut self, surface: Surface2D) -> Self {
+        self.current_panel().surfaces.push(surface);
+        self
+    }
+
+    /// Adds a histogram, with its optional density overlay, to the current panel.
+    pub fn histogram(mut self, histogram: Histogram) -> Self {
+        self.current_panel().histograms.push(histogram);
+        self
+    }
+
+    /// Adds a series of bars to the current panel.
+    pub fn bars(mut self, bars: Bars) -> Self {
+        self.current_panel().bars.push(bars);
+        self
+    }
+
+    /// Adds a candlestick chart to the current panel.
+    pub fn candlesticks(mut self, candlesticks: Candlesticks) -> Self {
+        self.current_panel().candlesticks.push(candlesticks);
+        self
+    }
+
+    /// Adds boxes or violins of samples grouped by category to the current panel.
+    pub fn distributions(mut self, distributions: Distributions) -> Self {
+        self.current_panel().distributions.push(distributions);
+        self
+    }
+
+    /// Adds the quantile bands and median of an ensemble of paths to the current panel.
+    pub fn fan_chart(self, fan_chart: FanChart) -> Self {
+        self.regions(fan_chart.regions()).curve(fan_chart.median())
+    }
+
+    /// Adds a label, reference line, span or point to the current panel.
+    pub fn annotation(mut self, annotation: Annotation) -> Self {
+        self.current_panel().annotations.push(annotation);
+        self
+    }
+
+    /// Adds several annotations to the current panel.
+    pub fn annotations(mut self, annotations: Vec<Annotation>) -> Self {
+        self.current_panel().annotations.extend(annotations);
+        self
+    }
+
+    /// Adds a filled region to the current panel.
+    pub fn region(mut self, region: Region) -> Self {
+        self.current_panel().regions.push(region);
+        self
+    }
+
+    /// Adds several filled regions to the current panel.
+    pub fn regions(mut self, regions: Vec<Region>) -> Self {
+        self.current_panel().regions.extend(regions);
+        self
+    }
+
+    /// Sets the axes labels and limits of the current panel.
+    pub fn axes(mut self, axes: Axes) -> Self {
+        self.current_panel().axes = axes;
+        self
+    }
+
+    /// Adds a panel, which becomes the current panel.
+    pub fn panel(mut self, panel: Panel) -> Self {
+        self.panels.push(panel);
+        self
+    }
+
+    /// Arranges the panels in a grid.
+    pub fn grid(mut self, grid: Grid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    /// Sets whether bars sharing a category are grouped side by side or stacked.
+    pub fn bar_mode(mut self, bar_mode: BarMode) -> Self {
+        self.bar_mode = bar_mode;
+        self
+    }
+
+    /// Sets the title of the figure.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the palette, font sizes, line widths and layout, the Primitive theme otherwise.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Sets the placement, orientation, grouping and title of the legend, or hides it.
+    pub fn legend(mut self, legend: LegendLayout) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Sets how titles, labels, ticks, legend names and annotations are rendered, LaTeX otherwise.
+    pub fn text_mode(mut self, text_mode: TextMode) -> Self {
+        self.text_mode = text_mode;
+        self
+    }
+
+    /// Resizes the figure to a preset or free size, scaling the fonts, lines and margins of the
+    /// theme along with it, whether the theme is set before or after.
+    pub fn size(mut self, size: FigureSize) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the display mode, transparency and whether the plot is shown.
+    pub fn display(mut self, display: Display) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Sets the file the plot is written to, `plot.html` otherwise.
+    ///
+    /// When exporting an image the extension is replaced by the one of the format.
+    pub fn save_as(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Makes [`Figure::plot`] export a static image rather than html.
+    pub fn export(mut self, export: ImageExport) -> Self {
+        self.export = Some(export);
+        self
+    }
+
+    /// Allows [`Figure::write_html`] and [`Figure::write_image`] to replace an existing file.
+    ///
+    /// [`Figure::plot`] ignores it and always replaces its file.
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Validates the figure and returns the plot without writing or showing it.
+    pub fn build(&self) -> Result<Plot, FigureError> {
+        self.validate()?;
+        Ok(self.render(self.display.transparent))
+    }
+
+    /// Builds the figure and writes it as html to `path`.
+    pub fn write_html(&self, path: impl AsRef<Path>) -> Result<Plot, FigureError> {
+        let path = path.as_ref();
+        if path.exists() && !self.overwrite {
+            return Err(FigureError::FileExists(path.to_path_buf()));
+        }
+        let plot = self.build()?;
+        fs::write(path, plot.to_html())?;
+        Ok(plot)
+    }
+
+    /// Builds the figure and exports it as a static image to `path`.
+    ///
+    /// The extension of `path` is replaced by the one of the chosen format.
+    /// Formats without transparency are drawn on the solid background of the display mode.
+    pub fn write_image(
+        &self,
+        path: impl AsRef<Path>,
+        export: ImageExport,
+    ) -> Result<Plot, FigureError> {
+        let path = path.as_ref().with_extension(export.format.extension());
+        if path.exists() && !self.overwrite {
+            return Err(FigureError::FileExists(path));
+        }
+        self.validate()?;
+        export.validate()?;
+        self.export_image(&path, export)
+    }
+
+    /// Renders the figure, writes it to file and shows it if requested.
+    ///
+    /// The file is always replaced, whatever [`Figure::overwrite`] says,
+    /// so that rerunning a script refreshes its plot.
+    /// Panics if the figure is invalid or kaleido fails to export it,
+    /// use [`Figure::build`] and [`Figure::write_image`] to handle errors instead.
+    pub fn plot(self) {
+        if let Err(error) = self.validate() {
+            panic!("{}", error);
+        }
+        let plot = match self.export {
+            Some(export) => {
+                let file = Path::new(self.file_name.as_deref().unwrap_or("plot"))
+                    .with_extension(export.format.extension());
+                match self.export_image(&file, export) {
+                    Ok(plot) => plot,
+                    Err(error) => panic!("{}", error),
+                }
+            }
+            None => {
+                let plot = self.render(self.display.transparent);
+                plot.write_html(self.file_name.as_deref().unwrap_or("plot.html"));
+                plot
+            }
+        };
+        if self.display.show {
+            plot.show();
+        }
+    }
+
+    /// Renders the figure and has kaleido write it to `path` as a static image.
+    ///
+    /// The kaleido crate panics when its binary is missing and reports some failures only by
+    /// not writing the file, so both are turned into [`FigureError::Export`] here.
+    fn export_image(&self, path: &Path, export: ImageExport) -> Result<Plot, FigureError> {
+        let failed = |reason: String| FigureError::Export {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let plot = self.render(self.display.transparent && export.format.supports_transparency());
+        let sizes = self.sized_theme().layout;
+        let format: plotly::ImageFormat = export.format.into();
+        let json = serde_json::to_value(&plot).map_err(|error| failed(error.to_string()))?;
+        let modified = |path: &Path| fs::metadata(path).and_then(|file| file.modified()).ok();
+        let before = modified(path);
+        let saved = panic::catch_unwind(AssertUnwindSafe(|| {
+            Kaleido::new()
+                .save(
+                    path,
+                    &json,
+                    &format.to_string(),
+                    sizes.width,
+                    sizes.height,
+                    export.scale,
+                )
+                .map_err(|error| error.to_string())
+        }));
+        match saved {
+            Ok(Ok(())) => match modified(path) {
+                Some(after) if Some(after) != before => Ok(plot),
+                _ => Err(failed("kaleido did not write an image".to_string())),
+            },
+            Ok(Err(reason)) => Err(failed(reason)),
+            Err(payload) => Err(failed(
+                payload
+                    .downcast_ref::<&str>()
+                    .map(|reason| reason.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "kaleido panicked".to_string()),
+            )),
+        }
+    }
+
+    /// Returns the panel that figure level curves, regions and axes go to.
+    fn current_panel(&mut self) -> &mut Panel {
+        if self.panels.is_empty() {
+            self.panels.push(Panel::default());
+        }
+        self.panels.last_mut().unwrap()
+    }
+
+    /// The grid the panels are laid out on.
+    fn layout_grid(&self) -> Grid {
+        self.grid.unwrap_or(Grid {
+            rows: self.panels.len().max(1),
+            columns: 1,
+            shared: SharedAxes::Independent,
+        })
+    }
+
+    /// Assigns each panel its grid cell and axes.
+    ///
+    /// Secondary y-axes are numbered after all of the primary ones.
+    fn panel_axes(&self) -> Vec<PanelAxes> {
+        let grid = self.layout_grid();
+        let mut panel_axes = (0..self.panels.len())
+            .map(|index| {
+                let row = index / grid.columns;
+                let column = index % grid.columns;
+                let (x, y) = match grid.shared {
+                    SharedAxes::Independent => (index + 1, index + 1),
+                    SharedAxes::X => (column + 1, index + 1),
+                    SharedAxes::Y => (index + 1, row + 1),
+                    SharedAxes::Both => (column + 1, row + 1),
+                };
+                PanelAxes {
+                    x,
+                    y,
+                    secondary_y: None,
+                    row,
+                    column,
+                }
+            })
+            .collect::<Vec<PanelAxes>>();
+        let mut next_y = panel_axes.iter().map(|axes| axes.y).max().unwrap_or(0) + 1;
+        for (panel, axes) in self.panels.iter().zip(panel_axes.iter_mut()) {
+            if panel.has_secondary_axis() {
+                axes.secondary_y = Some(next_y);
+                next_y += 1;
+            }
+        }
+        panel_axes
+    }
+
+    /// Checks the theme, the size, the image export, the grid and every panel.
+    fn validate(&self) -> Result<(), FigureError> {
+        self.theme.validate()?;
+        if let Some(export) = &self.export {
+            export.validate()?;
+        }
+        if let Some(size) = self.size {
+            let (width, height) = size.dimensions();
+            if width == 0 || height == 0 {
+                return Err(FigureError::InvalidSize { width, height });
+            }
+        }
+        let grid = self.layout_grid();
+        if self.panels.len() > grid.rows * grid.columns {
+            return Err(FigureError::GridTooSmall {
+                panels: self.panels.len(),
+                cells: grid.rows * grid.columns,
+            });
+        }
+        for axes in self.panel_axes() {
+            let highest = axes.x.max(axes.y).max(axes.secondary_y.unwrap_or(0));
+            if highest > MAX_AXES {
+                return Err(FigureError::TooManyAxes(highest));
+            }
+        }
+        for panel in self.panels.iter() {
+            panel.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Collects the traces and layout into a plot.
+    fn render(&self, transparent: bool) -> Plot {
+        let theme = self.sized_theme();
+        let skin = self.skin(&theme);
+        let mut plot = Plot::new();
+        let mut color_bar_x = match self.panels.iter().any(Panel::has_secondary_axis) {
+            true => 1.02 + COLOR_BAR_GAP,
+            false => 1.02,
+        };
+        for (panel, axes) in self.panels.iter().zip(self.panel_axes()) {
+            let (x_axis, y_axis) = (axis_id("x", axes.x), axis_id("y", axes.y));
+            for surface in panel.surfaces.iter() {
+                plot.add_trace(
+                    surface
+                        .heat_map(skin, color_bar_x)
+                        .x_axis(&x_axis)
+                        .y_axis(&y_axis),
+                );
+                if let Some(contour) = surface.contour(skin) {
+                    plot.add_trace(contour.x_axis(&x_axis).y_axis(&y_axis));
+                }
+                color_bar_x += COLOR_BAR_GAP;
+            }
+            for region in panel.regions.iter() {
+                plot.add_trace(region.trace(skin).x_axis(&x_axis).y_axis(&y_axis));
+            }
+            for histogram in panel.histograms.iter() {
+                plot.add_trace(histogram.trace(skin).x_axis(&x_axis).y_axis(&y_axis));
+                if let Some(density) = histogram.density_curve() {
+                    for trace in density.traces(skin) {
+                        plot.add_trace(trace.x_axis(&x_axis).y_axis(&y_axis));
+                    }
+                }
+            }
+            for bars in panel.bars.iter() {
+                plot.add_trace(bars.trace(skin, &x_axis, &y_axis));
+            }
+            for candlesticks in panel.candlesticks.iter() {
+                plot.add_trace(candlesticks.trace(skin, &x_axis, &y_axis));
+            }
+            for distributions in panel.distributions.iter() {
+                plot.add_trace(distributions.trace(skin, &x_axis, &y_axis));
+            }
+            for curve in panel.curves.iter() {
+                for trace in curve.traces(skin) {
+                    plot.add_trace(trace.x_axis(&x_axis).y_axis(&y_axis));
+                }
+            }
+            for family in panel.families.iter() {
+                for trace in family.traces(skin) {
+                    plot.add_trace(trace.x_axis(&x_axis).y_axis(&y_axis));
+                }
+                plot.add_trace(
+                    family
+                        .color_bar(skin, color_bar_x)
+                        .x_axis(&x_axis)
+                        .y_axis(&y_axis),
+                );
+                color_bar_x += COLOR_BAR_GAP;
+            }
+            if let Some(secondary_y) = axes.secondary_y {
+                let secondary_axis = axis_id("y", secondary_y);
+                for curve in panel.secondary_curves.iter() {
+                    for trace in curve.traces(skin) {
+                        plot.add_trace(trace.x_axis(&x_axis).y_axis(&secondary_axis));
+                    }
+                }
+            }
+            for annotation in panel.annotations.iter() {
+                if let Some(point) = annotation.trace(skin) {
+                    plot.add_trace(point.x_axis(&x_axis).y_axis(&y_axis));
+                }
+            }
+        }
+        plot.set_layout(self.layout(skin, transparent));
+        plot
+    }
+
+    /// Builds a branded x-axis for the given label and limits.
+    fn x_axis(&self, skin: Skin, label: &str, bounds: &[f64], scale: Scale) -> Axis {
+        let fonts = &skin.theme.fonts;
+        let x_label = skin.typeset(label, &skin.theme.latex.axis_label);
+        let x_axis = Axis::new()
+            .title(Title::new(&x_label).font(Font::new().size(fonts.x_label)))
+            .show_grid(true)
+            .grid_color(skin.resolve(&Color::Grey, MAIN_COLOR_SLOT))
+            .zero_line(false)
+            .tick_font(Font::new().size(fonts.ticks))
+            .auto_margin(false)
+            .ticks(plotly::layout::TicksDirection::Outside)
+            .range_slider(RangeSlider::new().visible(false));
+        let x_axis = scaled(x_axis, scale, bounds, skin);
+        x_axis
+            .color(skin.foreground())
+            .line_color(skin.foreground())
+    }
+
+    /// Builds a branded y-axis for the given label and limits.
+    fn y_axis(&self, skin: Skin, label: &str, bounds: &[f64], scale: Scale) -> Axis {
+        let fonts = &skin.theme.fonts;
+        let y_label = skin.typeset(label, &skin.theme.latex.axis_label);
+        let y_axis = Axis::new()
+            .title(Title::new(&y_label).font(Font::new().size(fonts.y_label)))
+            .show_grid(true)
+            .grid_color(skin.resolve(&Color::Grey, MAIN_COLOR_SLOT))
+            .zero_line(false)
+            .show_line(true)
+            .tick_font(Font::new().size(fonts.ticks))
+            .auto_margin(false)
+            .ticks(plotly::layout::TicksDirection::Outside);
+        let y_axis = scaled(y_axis, scale, bounds, skin);
+        y_axis
+            .color(skin.foreground())
+            .line_color(skin.foreground())
+    }
+
+    /// The panel each x and y axis id takes its labels and scale from, if any.
+    fn axis_owners(&self, panel_axes: &[PanelAxes]) -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+        let mut x_owners: Vec<Option<usize>> = vec![None; MAX_AXES + 1];
+        let mut y_owners: Vec<Option<usize>> = vec![None; MAX_AXES + 1];
+        for (index, axes) in panel_axes.iter().enumerate() {
+            x_owners[axes.x] = Some(index);
+            y_owners[axes.y].get_or_insert(index);
+        }
+        if self.panels.len() <= 1 {
+            x_owners[1] = Some(0);
+            y_owners[1] = Some(0);
+        }
+        (x_owners, y_owners)
+    }
+
+    /// Places one axis per distinct axis id, positioned and anchored for its panel.
+    ///
+    /// A shared x-axis takes its labels from the bottom panel of its column,
+    /// a shared y-axis from the first panel of its row.
+    fn set_axes(&self, layout: Layout, skin: Skin) -> Layout {
+        let grid = self.layout_grid();
+        let panel_axes = self.panel_axes();
+        let single = self.panels.len() <= 1;
+        let default_panel = Panel::default();
+        let (x_owners, y_owners) = self.axis_owners(&panel_axes);
+        let panel = |index: usize| self.panels.get(index).unwrap_or(&default_panel);
+
+        let mut layout = layout;
+        for (id, owner) in x_owners.iter().enumerate() {
+            let Some(owner) = owner else { continue };
+            let axes = &panel(*owner).axes;
+            let scale = panel(*owner).scales().0;
+            let bounds = match axes.bounds.0.is_empty() {
+                true => axes.auto_ranges.0.fit(
+                    self.panels
+                        .iter()
+                        .zip(panel_axes.iter())
+                        .filter(|(_, axes)| axes.x == id)
+                        .flat_map(|(panel, _)| panel.x_values(self.bar_mode)),
+                    scale,
+                ),
+                false => axes.bounds.0.clone(),
+            };
+            let axis = self.x_axis(skin, &axes.x_label, &bounds, scale);
+            let axis = match (single, panel_axes.get(*owner)) {
+                (false, Some(axes)) => axis
+                    .anchor(axis_id("y", axes.y))
+                    .domain(&domain(axes.column, grid.columns)),
+                _ => axis,
+            };
+            layout = set_x_axis(layout, id, axis);
+        }
+        for (id, owner) in y_owners.iter().enumerate() {
+            let Some(owner) = owner else { continue };
+            let axes = &panel(*owner).axes;
+            let scale = panel(*owner).scales().1;
+            let bounds = match axes.bounds.1.is_empty() {
+                true => axes.auto_ranges.1.fit(
+                    self.panels
+                        .iter()
+                        .zip(panel_axes.iter())
+                        .filter(|(_, axes)| axes.y == id)
+                        .flat_map(|(panel, _)| panel.y_values(self.bar_mode)),
+                    scale,
+                ),
+                false => axes.bounds.1.clone(),
+            };
+            let axis = self.y_axis(skin, &axes.y_label, &bounds, scale);
+            let axis = match (single, panel_axes.get(*owner)) {
+                (false, Some(axes)) => axis
+                    .anchor(axis_id("x", axes.x))
+                    .domain(&domain(grid.rows - 1 - axes.row, grid.rows)),
+                _ => axis,
+            };
+            layout = set_y_axis(layout, id, axis);
+        }
+        for (panel, axes) in self.panels.iter().zip(panel_axes.iter()) {
+            let Some(secondary_y) = axes.secondary_y else {
+                continue;
+            };
+            let default_secondary = SecondaryAxis::default();
+            let secondary = panel
+                .axes
+                .secondary_y
+                .as_ref()
+                .unwrap_or(&default_secondary);
+            let bounds = match secondary.bounds.is_empty() {
+                true => secondary
+                    .auto_range
+                    .fit(panel.secondary_y_values(), secondary.scale),
+                false => secondary.bounds.clone(),
+            };
+            let axis = self.y_axis(skin, &secondary.label, &bounds, secondary.scale);
+            let axis = axis
+                .overlaying(axis_id("y", axes.y))
+                .anchor(axis_id("x", axes.x))
+                .side(AxisSide::Right)
+                .show_grid(false);
+            layout = set_y_axis(layout, secondary_y, axis);
+        }
+        layout
+    }
+
+    /// Adds the lines, spans and text of every annotation to the layout.
+    fn annotate(&self, layout: Layout, skin: Skin) -> Layout {
+        let mut layout = self.set_axes(layout, skin);
+        let panel_axes = self.panel_axes();
+        let (x_owners, y_owners) = self.axis_owners(&panel_axes);
+        let scale = |owners: &[Option<usize>], id: usize, scale: fn((Scale, Scale)) -> Scale| {
+            owners[id]
+                .and_then(|owner| self.panels.get(owner))
+                .map_or(Scale::Linear, |panel| scale(panel.scales()))
+        };
+        for (panel, axes) in self.panels.iter().zip(panel_axes.iter()) {
+            let (x_axis, y_axis) = (axis_id("x", axes.x), axis_id("y", axes.y));
+            let scales = (
+                scale(&x_owners, axes.x, |scales| scales.0),
+                scale(&y_owners, axes.y, |scales| scales.1),
+            );
+            for annotation in panel.annotations.iter() {
+                if let Some(shape) = annotation.shape(skin, &x_axis, &y_axis) {
+                    layout.add_shape(shape);
+                }
+                if let Some(text) = annotation.text(skin, &x_axis, &y_axis, scales) {
+                    layout.add_annotation(text);
+                }
+            }
+        }
+        layout
+    }
+
+    /// The right margin, widened for a secondary y-axis, for every colorbar
+    /// and for a legend right of the plot.
+    fn right_margin(&self, skin: Skin) -> usize {
+        let sizes = &skin.theme.layout;
+        let secondary = match self.panels.iter().any(Panel::has_secondary_axis) {
+            true => sizes.secondary_axis_margin,
+            false => 0,
+        };
+        let color_bars = self.panels.iter().map(Panel::color_bars).sum::<usize>();
+        let legend = match self.legend.position {
+            LegendPosition::OutsideRight if !self.legend.hidden => sizes.legend_margin,
+            _ => 0,
+        };
+        sizes.right_margin + secondary + sizes.color_bar_margin * color_bars + legend
+    }
+
+    /// The bottom margin, widened for a legend below the plot.
+    fn bottom_margin(&self, skin: Skin) -> usize {
+        let sizes = &skin.theme.layout;
+        match self.legend.position {
+            LegendPosition::OutsideBottom if !self.legend.hidden => {
+                sizes.bottom_margin + sizes.legend_margin
+            }
+            _ => sizes.bottom_margin,
+        }
+    }
+
+    /// The position and anchors of the legend, or `None` to leave its placement to plotly.
+    fn legend_anchor(&self, skin: Skin) -> Option<((f64, Anchor), (f64, Anchor))> {
+        let left = || (LEGEND_INSET, Anchor::Left);
+        let right = || (1.0 - LEGEND_INSET, Anchor::Right);
+        let top = || (1.0 - LEGEND_INSET, Anchor::Top);
+        let bottom = || (LEGEND_INSET, Anchor::Bottom);
+        match self.legend.position {
+            LegendPosition::Auto => None,
+            LegendPosition::TopLeft => Some((left(), top())),
+            LegendPosition::TopRight => Some((right(), top())),
+            LegendPosition::BottomLeft => Some((left(), bottom())),
+            LegendPosition::BottomRight => Some((right(), bottom())),
+            LegendPosition::OutsideRight => {
+                let secondary = self.panels.iter().any(Panel::has_secondary_axis) as usize;
+                let color_bars = self.panels.iter().map(Panel::color_bars).sum::<usize>();
+                let x = 1.02 + COLOR_BAR_GAP * (secondary + color_bars) as f64;
+                Some(((x, Anchor::Left), (1.0, Anchor::Top)))
+            }
+            LegendPosition::OutsideBottom => {
+                let sizes = &skin.theme.layout;
+                let plot_height =
+                    sizes.height as f64 - (sizes.top_margin + self.bottom_margin(skin)) as f64;
+                // A figure shorter than its margins has no plot area to offset the legend by.
+                let y = match plot_height > 0.0 {
+                    true => -(sizes.bottom_margin as f64) / plot_height,
+                    false => 0.0,
+                };
+                Some(((0.5, Anchor::Center), (y, Anchor::Top)))
+            }
+        }
+    }
+
+    /// Builds the legend with its placement, orientation, grouping and title.
+    fn legend_layout(&self, skin: Skin) -> Legend {
+        let legend = Legend::new().font(
+            Font::new()
+                .color(skin.foreground())
+                .size(skin.theme.fonts.legend),
+        );
+        let legend = match self.legend_anchor(skin) {
+            Some(((x, x_anchor), (y, y_anchor))) => {
+                legend.x(x).y(y).x_anchor(x_anchor).y_anchor(y_anchor)
+            }
+            None => legend.x_anchor(Anchor::Auto).y_anchor(Anchor::Auto),
+        };
+        let legend = match self.legend.orientation {
+            LegendOrientation::Vertical => legend,
+            LegendOrientation::Horizontal => legend.orientation(Orientation::Horizontal),
+        };
+        let legend = match self.legend.grouped {
+            true => legend.trace_order(TraceOrder::Grouped),
+            false => legend,
+        };
+        match &self.legend.title {
+            Some(title) => legend.title(
+                Title::new(&skin.typeset(title, &skin.theme.latex.legend))
+                    .font(Font::new().size(skin.theme.fonts.legend)),
+            ),
+            None => legend,
+        }
+    }
+
+    /// The theme of the figure scaled to its size, if one is set.
+    fn sized_theme(&self) -> Theme {
+        match self.size {
+            Some(size) => self.theme.clone().sized(size),
+            None => self.theme.clone(),
+        }
+    }
+
+    /// The given theme in the display mode and text mode of the figure.
+    fn skin<'a>(&self, theme: &'a Theme) -> Skin<'a> {
+        Skin {
+            theme,
+            mode: self.display.mode,
+            text: self.text_mode,
+            grouped_legend: self.legend.grouped,
+        }
+    }
+
+    /// Builds the branded layout around the traces.
+    fn layout(&self, skin: Skin, transparent: bool) -> Layout {
+        let sizes = &skin.theme.layout;
+        let title = skin.typeset(&self.title, &skin.theme.latex.title);
+        let layout = Layout::new()
+            .title(Title::new(title.as_str()))
+            .width(sizes.width)
+            .height(sizes.height)
+            .margin(
+                Margin::new()
+                    .bottom(self.bottom_margin(skin))
+                    .left(sizes.left_margin)
+                    .top(sizes.top_margin)
+                    .right(self.right_margin(skin)),
+            );
+        let layout = self
+            .annotate(layout, skin)
+            .bar_mode(match self.bar_mode {
+                BarMode::Grouped => plotly::layout::BarMode::Group,
+                BarMode::Stacked => plotly::layout::BarMode::Relative,
+            })
+            .box_mode(plotly::layout::BoxMode::Group)
+            .violin_mode(plotly::layout::ViolinMode::Group);
+        let layout = match transparent {
+            true => layout
+                .plot_background_color("rgba(0,0,0,0)")
+                .paper_background_color("rgba(0,0,0,0)"),
+            false => layout
+                .plot_background_color(skin.background())
+                .paper_background_color(skin.background()),
+        };
+        layout
+            .show_legend(!self.legend.hidden)
+            .legend(self.legend_layout(skin))
+            .font(Font::new().color(skin.foreground()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn themes_set_in_code_are_validated() {
+        let mut theme = Theme::default();
+        theme.palette.greens[5] = String::from("abc");
+        let figure = Figure::new().theme(theme);
+        assert!(matches!(figure.build(), Err(FigureError::InvalidColor(color)) if color == "abc"));
+        let mut theme = Theme::default();
+        theme.latex.title = String::from("\\huge");
+        let figure = Figure::new().theme(theme);
+        assert!(matches!(figure.build(), Err(FigureError::Theme(_))));
+    }
+
+    fn layout_size(figure: Figure) -> (u64, u64, u64) {
+        let plot = figure.build().ok().unwrap();
+        let json = serde_json::to_value(&plot).unwrap();
+        let layout = &json["layout"];
+        (
+            layout["width"].as_u64().unwrap(),
+            layout["height"].as_u64().unwrap(),
+            layout["margin"]["l"].as_u64().unwrap(),
+        )
+    }
+
+    #[test]
+    fn size_applies_whether_set_before_or_after_the_theme() {
+        let before = layout_size(
+            Figure::new()
+                .size(FigureSize::Slide)
+                .theme(Theme::default()),
+        );
+        let after = layout_size(
+            Figure::new()
+                .theme(Theme::default())
+                .size(FigureSize::Slide),
+        );
+        assert_eq!(before, after);
+        assert_eq!((before.0, before.1), (1920, 1080));
+        assert!(before.2 > Theme::default().layout.left_margin as u64);
+    }
+
+    #[test]
+    fn zero_sizes_are_rejected() {
+        for (width, height) in [(0, 900), (1200, 0)] {
+            let figure = Figure::new().size(FigureSize::Custom { width, height });
+            assert!(matches!(
+                figure.build(),
+                Err(FigureError::InvalidSize { width: w, height: h }) if (w, h) == (width, height)
+            ));
+        }
+    }
+
+    #[test]
+    fn explicit_legend_groups_apply_without_grouping() {
+        let design = CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        );
+        let curve = || Curve::new(vec![0.0, 1.0], vec![0.0, 1.0], design.clone());
+        let plot = Figure::new()
+            .curve(curve().name("first").legend_group("pair"))
+            .curve(curve().legend_group("pair"))
+            .curve(curve().name("single"))
+            .build()
+            .ok()
+            .unwrap();
+        let json = serde_json::to_value(&plot).unwrap();
+        let groups = json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|trace| trace["legendgroup"].as_str())
+            .collect::<Vec<Option<&str>>>();
+        assert_eq!(groups, vec![Some("pair"), Some("pair"), None]);
+    }
+
+    #[test]
+    fn outside_bottom_legend_fits_a_figure_shorter_than_its_margins() {
+        let legend = LegendLayout {
+            position: LegendPosition::OutsideBottom,
+            ..Default::default()
+        };
+        let size = FigureSize::Custom {
+            width: 4000,
+            height: 300,
+        };
+        let plot = Figure::new()
+            .size(size)
+            .legend(legend)
+            .build()
+            .ok()
+            .unwrap();
+        let json = serde_json::to_value(&plot).unwrap();
+        assert_eq!(json["layout"]["legend"]["y"].as_f64(), Some(0.0));
+    }
+
+    fn log_x_axis(bounds: Vec<f64>) -> serde_json::Value {
+        let axes = Axes {
+            bounds: (bounds, vec![]),
+            scales: (Scale::Log, Scale::Linear),
+            ..Default::default()
+        };
+        let plot = Figure::new().axes(axes).build().ok().unwrap();
+        serde_json::to_value(&plot).unwrap()["layout"]["xaxis"].clone()
+    }
+
+    #[test]
+    fn log_axes_within_a_decade_keep_plotly_ticks() {
+        let axis = log_x_axis(vec![2.0, 5.0]);
+        assert!(axis["tickvals"].is_null());
+        assert!(axis["tickmode"].is_null());
+        let range = axis["range"].as_array().unwrap();
+        assert!((range[0].as_f64().unwrap() - 2_f64.log10()).abs() < 1e-12);
+        assert!((range[1].as_f64().unwrap() - 5_f64.log10()).abs() < 1e-12);
+        assert_eq!(axis["tickprefix"], "$\\LARGE{");
+    }
+
+    #[test]
+    fn log_axes_across_decades_tick_every_visible_power_of_ten() {
+        let axis = log_x_axis(vec![0.5, 2000.0]);
+        let values = axis["tickvals"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_f64().unwrap())
+            .collect::<Vec<f64>>();
+        assert_eq!(values, vec![1.0, 10.0, 100.0, 1000.0]);
+        assert_eq!(axis["ticktext"][0], "$\\LARGE{10^{0}}$");
+        assert_eq!(axis["ticktext"][3], "$\\LARGE{10^{3}}$");
+    }
+
+    #[test]
+    fn bars_and_distributions_get_category_axes() {
+        let series = |orientation| Bars {
+            categories: vec![String::from("a"), String::from("b")],
+            values: vec![1.0, 2.0],
+            errors: None,
+            orientation,
+            design: RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+            name: None,
+            legend_group: None,
+        };
+        let axis_types = |figure: Figure| {
+            let json = serde_json::to_value(figure.build().ok().unwrap()).unwrap();
+            let layout = &json["layout"];
+            (
+                layout["xaxis"]["type"].as_str().unwrap().to_string(),
+                layout["yaxis"]["type"].as_str().unwrap().to_string(),
+            )
+        };
+        let vertical = axis_types(Figure::new().bars(series(BarOrientation::Vertical)));
+        assert_eq!(vertical, ("category".to_string(), "linear".to_string()));
+        let horizontal = axis_types(Figure::new().bars(series(BarOrientation::Horizontal)));
+        assert_eq!(horizontal, ("linear".to_string(), "category".to_string()));
+        let distributions = Distributions {
+            samples: vec![(String::from("a"), vec![1.0, 2.0])],
+            style: crate::distributions::DistributionStyle::Box,
+            design: RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+            name: None,
+            legend_group: None,
+        };
+        let boxes = axis_types(Figure::new().distributions(distributions));
+        assert_eq!(boxes, ("category".to_string(), "linear".to_string()));
+        let dates = Axes {
+            scales: (Scale::Date, Scale::Linear),
+            ..Default::default()
+        };
+        let explicit = axis_types(
+            Figure::new()
+                .axes(dates)
+                .bars(series(BarOrientation::Vertical)),
+        );
+        assert_eq!(explicit, ("date".to_string(), "linear".to_string()));
+    }
+
+    fn line() -> Curve {
+        let design = CurveDesign::new(
+            Color::Green,
+            MAIN_COLOR_SLOT,
+            Style::Lines(LineEmphasis::Light),
+        );
+        Curve::new(vec![1.0, 2.0], vec![1.0, 2.0], design)
+    }
+
+    fn grid_json(rows: usize, columns: usize, shared: SharedAxes) -> serde_json::Value {
+        let figure = (0..rows * columns).fold(
+            Figure::new().grid(Grid {
+                rows,
+                columns,
+                shared,
+            }),
+            |figure, _| figure.panel(Panel::new().curve(line())),
+        );
+        serde_json::to_value(figure.build().ok().unwrap()).unwrap()
+    }
+
+    fn trace_axes(json: &serde_json::Value) -> Vec<(&str, &str)> {
+        json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|trace| {
+                (
+                    trace["xaxis"].as_str().unwrap(),
+                    trace["yaxis"].as_str().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn assert_domain(axis: &serde_json::Value, expected: [f64; 2]) {
+        let domain = axis["domain"].as_array().unwrap();
+        for (actual, expected) in domain.iter().zip(expected) {
+            assert!((actual.as_f64().unwrap() - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn panels_in_a_column_share_the_x_axis() {
+        let json = grid_json(2, 1, SharedAxes::X);
+        assert_eq!(trace_axes(&json), vec![("x", "y"), ("x", "y2")]);
+        let layout = &json["layout"];
+        assert_eq!(layout["xaxis"]["anchor"], "y2");
+        assert_domain(&layout["xaxis"], [0.0, 1.0]);
+        assert!(layout["xaxis2"].is_null());
+        assert_eq!(layout["yaxis"]["anchor"], "x");
+        assert_domain(&layout["yaxis"], [0.54, 1.0]);
+        assert_eq!(layout["yaxis2"]["anchor"], "x");
+        assert_domain(&layout["yaxis2"], [0.0, 0.46]);
+    }
+
+    #[test]
+    fn panels_share_x_axes_by_column_and_y_axes_by_row() {
+        let json = grid_json(2, 2, SharedAxes::Both);
+        assert_eq!(
+            trace_axes(&json),
+            vec![("x", "y"), ("x2", "y"), ("x", "y2"), ("x2", "y2")]
+        );
+        let layout = &json["layout"];
+        for (x_axis, domain) in [("xaxis", [0.0, 0.46]), ("xaxis2", [0.54, 1.0])] {
+            assert_eq!(layout[x_axis]["anchor"], "y2");
+            assert_domain(&layout[x_axis], domain);
+        }
+        for (y_axis, domain) in [("yaxis", [0.54, 1.0]), ("yaxis2", [0.0, 0.46])] {
+            assert_eq!(layout[y_axis]["anchor"], "x");
+            assert_domain(&layout[y_axis], domain);
+        }
+        assert!(layout["xaxis3"].is_null() && layout["yaxis3"].is_null());
+    }
+
+    #[test]
+    fn grids_too_small_or_with_too_many_axes_are_rejected() {
+        let figure = Figure::new()
+            .grid(Grid {
+                rows: 1,
+                columns: 1,
+                shared: SharedAxes::Independent,
+            })
+            .panel(Panel::new())
+            .panel(Panel::new());
+        assert!(matches!(
+            figure.build(),
+            Err(FigureError::GridTooSmall {
+                panels: 2,
+                cells: 1
+            })
+        ));
+        let figure = (0..9).fold(
+            Figure::new().grid(Grid {
+                rows: 3,
+                columns: 3,
+                shared: SharedAxes::Independent,
+            }),
+            |figure, _| figure.panel(Panel::new()),
+        );
+        assert!(matches!(figure.build(), Err(FigureError::TooManyAxes(9))));
+        let shared = grid_json(3, 3, SharedAxes::Both);
+        assert_eq!(trace_axes(&shared)[8], ("x3", "y3"));
+    }
+
+    #[test]
+    fn image_exports_need_a_positive_scale() {
+        for scale in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let export = ImageExport {
+                scale,
+                ..Default::default()
+            };
+            let path = std::env::temp_dir().join(format!("scale-{}.png", std::process::id()));
+            assert!(matches!(
+                Figure::new().write_image(&path, export),
+                Err(FigureError::InvalidScale(_))
+            ));
+            assert!(!path.exists());
+            assert!(matches!(
+                Figure::new().export(export).build(),
+                Err(FigureError::InvalidScale(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn write_image_refuses_to_overwrite_by_default() {
+        let path = std::env::temp_dir().join(format!("existing-{}.png", std::process::id()));
+        fs::write(&path, "image").unwrap();
+        let written = Figure::new().write_image(path.with_extension("svg"), ImageExport::default());
+        assert!(matches!(written, Err(FigureError::FileExists(existing)) if existing == path));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "image");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn write_html_refuses_to_overwrite_by_default() {
+        let path = std::env::temp_dir().join(format!("existing-{}.html", std::process::id()));
+        fs::write(&path, "html").unwrap();
+        let written = Figure::new().write_html(&path);
+        assert!(matches!(written, Err(FigureError::FileExists(existing)) if existing == path));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "html");
+        assert!(Figure::new().overwrite(true).write_html(&path).is_ok());
+        assert_ne!(fs::read_to_string(&path).unwrap(), "html");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn secondary_axes_are_numbered_after_the_primary_ones() {
+        let figure = Figure::new()
+            .panel(Panel::new().curve(line()).secondary_curve(line()))
+            .panel(Panel::new().curve(line()))
+            .panel(Panel::new().curve(line()).secondary_curve(line()));
+        let json = serde_json::to_value(figure.build().ok().unwrap()).unwrap();
+        assert_eq!(
+            trace_axes(&json),
+            vec![
+                ("x", "y"),
+                ("x", "y4"),
+                ("x2", "y2"),
+                ("x3", "y3"),
+                ("x3", "y5")
+            ]
+        );
+        let layout = &json["layout"];
+        for (secondary, primary, x_axis) in [("yaxis4", "y", "x"), ("yaxis5", "y3", "x3")] {
+            assert_eq!(layout[secondary]["overlaying"], primary);
+            assert_eq!(layout[secondary]["anchor"], x_axis);
+            assert_eq!(layout[secondary]["side"], "right");
+            assert_eq!(layout[secondary]["showgrid"], false);
+        }
+        assert!(layout["yaxis6"].is_null());
+    }
+
+    #[test]
+    fn secondary_axes_widen_the_right_margin() {
+        let theme = Theme::default();
+        let right_margin = |figure: Figure| {
+            let json = serde_json::to_value(figure.build().ok().unwrap()).unwrap();
+            json["layout"]["margin"]["r"].as_u64().unwrap() as usize
+        };
+        let primary = right_margin(Figure::new().curve(line()));
+        let secondary = right_margin(Figure::new().curve(line()).secondary_curve(line()));
+        assert_eq!(primary, theme.layout.right_margin);
+        assert_eq!(
+            secondary,
+            theme.layout.right_margin + theme.layout.secondary_axis_margin
+        );
+    }
+}
@@ -115,6 +115,27 @@ pub fn polynomial_approx(x: Vec<f64>, coeffs: Vec<f64>) -> Vec<f64> {
     }
     y
 }
+/// The `q`-th quantile of samples sorted in ascending order, interpolating between neighbours.
+#[allow(unused)]
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+/// The value at `at` of the piecewise linear curve through points with increasing x coordinates,
+/// held constant beyond the first and last points.
+#[allow(unused)]
+pub fn interpolate(x: &[f64], y: &[f64], at: f64) -> f64 {
+    if x.len() < 2 {
+        return y[0];
+    }
+    let upper = x.partition_point(|x| *x < at).clamp(1, x.len() - 1);
+    let (x_0, x_1, y_0, y_1) = (x[upper - 1], x[upper], y[upper - 1], y[upper]);
+    match x_1 > x_0 {
+        true => y_0 + (y_1 - y_0) * ((at - x_0) / (x_1 - x_0)).clamp(0.0, 1.0),
+        false => y_0,
+    }
+}
 #[allow(unused)]
 pub fn factorial(n: u32) -> u32 {
     let mut f = 1;
@@ -185,7 +206,7 @@ pub fn forced_rebalance(
     )
 }
 
-#[allow(unused)]
+#[allow(unused, clippy::needless_range_loop)]
 pub fn g3m_trading_curve(x_values: Vec<f64>, w: f64, l: f64) -> (Vec<f64>, Vec<f64>) {
     let n = x_values.len();
     let (x, mut y) = (x_values, Vec::with_capacity(n));
@@ -195,3 +216,31 @@ pub fn g3m_trading_curve(x_values: Vec<f64>, w: f64, l: f64) -> (Vec<f64>, Vec<f
     }
     (x, y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_between_points() {
+        let (x, y) = ([0.0, 1.0, 3.0], [0.0, 2.0, 6.0]);
+        assert_eq!(interpolate(&x, &y, 0.5), 1.0);
+        assert_eq!(interpolate(&x, &y, 2.0), 4.0);
+        assert_eq!(interpolate(&x, &y, 1.0), 2.0);
+    }
+
+    #[test]
+    fn interpolate_holds_the_first_and_last_points() {
+        let (x, y) = ([0.0, 1.0, 3.0], [0.0, 2.0, 6.0]);
+        assert_eq!(interpolate(&x, &y, 0.0), 0.0);
+        assert_eq!(interpolate(&x, &y, -5.0), 0.0);
+        assert_eq!(interpolate(&x, &y, 3.0), 6.0);
+        assert_eq!(interpolate(&x, &y, 10.0), 6.0);
+        assert_eq!(interpolate(&[2.0], &[7.0], 0.0), 7.0);
+    }
+
+    #[test]
+    fn interpolate_takes_the_first_of_repeated_coordinates() {
+        assert_eq!(interpolate(&[0.0, 1.0, 1.0], &[0.0, 1.0, 5.0], 1.0), 1.0);
+    }
+}
@@ -0,0 +1,336 @@
+//! Histograms of samples with an optional kernel density overlay.
+
+#![warn(missing_docs)]
+use itertools_num::linspace;
+use plotly::{
+    common::{Fill, Line, Mode},
+    Scatter,
+};
+
+use crate::{
+    design::*,
+    error::FigureError,
+    functions::quantile,
+    plot::{legend_name, validate_color_slot, Curve, Grouped},
+    theme::Skin,
+};
+
+/// The number of points the kernel density estimate is evaluated at.
+const DENSITY_POINTS: usize = 200;
+
+/// The most bins a histogram is split into, so a far outlier cannot exhaust memory.
+pub const MAX_BINS: usize = 10_000;
+
+/// How the range of the samples is split into bins.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum Bins {
+    /// A fixed number of equally wide bins.
+    Count(usize),
+    /// Bins of a fixed width, starting at the smallest sample.
+    Width(f64),
+    /// Bins twice the interquartile range wide over the cube root of the number of samples,
+    /// falling back to Sturges' rule when the interquartile range is zero
+    /// or would need more than [`MAX_BINS`] bins.
+    #[default]
+    FreedmanDiaconis,
+}
+
+/// What the height of each bin measures.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum Normalization {
+    /// The number of samples in the bin.
+    #[default]
+    Count,
+    /// The fraction of samples in the bin, summing to one over all bins.
+    Probability,
+    /// The fraction of samples per unit width, integrating to one over all bins.
+    Density,
+}
+
+/// A struct to hold samples drawn as a histogram.
+pub struct Histogram {
+    /// The samples to bin.
+    pub samples: Vec<f64>,
+    /// How the samples are binned.
+    pub bins: Bins,
+    /// What the height of each bin measures.
+    pub normalization: Normalization,
+    /// The color of the bins.
+    pub design: RegionDesign,
+    /// The design of a Gaussian kernel density estimate drawn over the bins, if any.
+    pub density: Option<CurveDesign>,
+    /// An optional name for the histogram that appears in the legend.
+    pub name: Option<String>,
+    /// An optional legend group, so the histogram toggles with its density curve.
+    pub legend_group: Option<String>,
+}
+
+/// The distance between the smallest and largest of the sorted samples.
+fn span(sorted: &[f64]) -> f64 {
+    sorted[sorted.len() - 1] - sorted[0]
+}
+
+/// The number of bins of the given width needed to cover a span, at least one.
+///
+/// Rounding error is shaved off so a span of exactly n widths does not open an empty bin.
+fn bin_count(span: f64, width: f64) -> f64 {
+    (span / width - 1e-9).ceil().max(1.0)
+}
+
+impl Histogram {
+    /// Checks that the bins can be built without exceeding [`MAX_BINS`].
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        match self.bins {
+            Bins::Count(count) if count == 0 || count > MAX_BINS => {
+                return Err(FigureError::InvalidBins)
+            }
+            Bins::Width(width) if width.is_nan() || width <= 0.0 => {
+                return Err(FigureError::InvalidBins)
+            }
+            Bins::Width(width) => {
+                let sorted = self.sorted();
+                if !sorted.is_empty() && bin_count(span(&sorted), width) > MAX_BINS as f64 {
+                    return Err(FigureError::InvalidBins);
+                }
+            }
+            _ => {}
+        }
+        if let Some(density) = &self.density {
+            validate_color_slot(&density.color, density.color_slot)?;
+        }
+        validate_color_slot(&self.design.color, self.design.color_slot)
+    }
+
+    /// The finite samples in ascending order.
+    fn sorted(&self) -> Vec<f64> {
+        let mut sorted = self
+            .samples
+            .iter()
+            .cloned()
+            .filter(|sample| sample.is_finite())
+            .collect::<Vec<f64>>();
+        sorted.sort_by(f64::total_cmp);
+        sorted
+    }
+
+    /// The width of every bin for the given sorted samples.
+    fn bin_width(&self, sorted: &[f64]) -> f64 {
+        let span = span(sorted);
+        let sturges = || span / ((sorted.len() as f64).log2().ceil() + 1.0);
+        let width = match self.bins {
+            Bins::Count(count) => span / count as f64,
+            Bins::Width(width) => width,
+            Bins::FreedmanDiaconis => {
+                let iqr = quantile(sorted, 0.75) - quantile(sorted, 0.25);
+                let width = 2.0 * iqr / (sorted.len() as f64).cbrt();
+                match iqr > 0.0 && bin_count(span, width) <= MAX_BINS as f64 {
+                    true => width,
+                    false => sturges(),
+                }
+            }
+        };
+        // Identical samples still get a bin of their own.
+        match width > 0.0 {
+            true => width,
+            false => 1.0,
+        }
+    }
+
+    /// The left edge and width of the bins along with the normalized height of each bin.
+    ///
+    /// The largest sample falls in the last bin rather than opening a bin of its own.
+    /// There are never more than [`MAX_BINS`] bins, samples past the last one are counted in it.
+    pub fn heights(&self) -> (f64, f64, Vec<f64>) {
+        let sorted = self.sorted();
+        if sorted.is_empty() {
+            return (0.0, 1.0, vec![]);
+        }
+        let width = self.bin_width(&sorted);
+        let start = sorted[0];
+        let count = match self.bins {
+            Bins::Count(bins) => bins,
+            _ => bin_count(span(&sorted), width).min(MAX_BINS as f64) as usize,
+        }
+        .clamp(1, MAX_BINS);
+        let mut heights = vec![0.0; count];
+        for sample in sorted.iter() {
+            let bin = (((sample - start) / width) as usize).min(count - 1);
+            heights[bin] += 1.0;
+        }
+        let scale = match self.normalization {
+            Normalization::Count => 1.0,
+            Normalization::Probability => 1.0 / sorted.len() as f64,
+            Normalization::Density => 1.0 / (sorted.len() as f64 * width),
+        };
+        (
+            start,
+            width,
+            heights.iter().map(|height| height * scale).collect(),
+        )
+    }
+
+    /// The Gaussian kernel density estimate, scaled to the normalization of the bins.
+    ///
+    /// The bandwidth follows Silverman's rule of thumb, and the curve joins the legend group
+    /// of the histogram.
+    pub(crate) fn density_curve(&self) -> Option<Curve> {
+        let design = self.density.as_ref()?;
+        let sorted = self.sorted();
+        if sorted.len() < 2 {
+            return None;
+        }
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let deviation = (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+        let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+        let spread = match iqr > 0.0 {
+            true => deviation.min(iqr / 1.34),
+            false => deviation,
+        };
+        let bandwidth = match spread > 0.0 {
+            true => 0.9 * spread * n.powf(-0.2),
+            false => 1.0,
+        };
+        let (_, width, _) = self.heights();
+        let scale = match self.normalization {
+            Normalization::Count => n * width,
+            Normalization::Probability => width,
+            Normalization::Density => 1.0,
+        };
+        let x_coordinates = linspace(
+            sorted[0] - 3.0 * bandwidth,
+            sorted[sorted.len() - 1] + 3.0 * bandwidth,
+            DENSITY_POINTS,
+        )
+        .collect::<Vec<f64>>();
+        let y_coordinates = x_coordinates
+            .iter()
+            .map(|x| {
+                let total = sorted
+                    .iter()
+                    .map(|sample| (-0.5 * ((x - sample) / bandwidth).powi(2)).exp())
+                    .sum::<f64>();
+                scale * total / (n * bandwidth * (2.0 * std::f64::consts::PI).sqrt())
+            })
+            .collect();
+        Some(Curve {
+            legend_group: self.legend_group.clone(),
+            ..Curve::new(x_coordinates, y_coordinates, design.clone())
+        })
+    }
+
+    /// The outline of the bins, one rectangle standing on zero per bin.
+    pub(crate) fn outline(&self) -> (Vec<f64>, Vec<f64>) {
+        let (start, width, heights) = self.heights();
+        let mut x_coordinates = vec![];
+        let mut y_coordinates = vec![];
+        for (bin, height) in heights.iter().enumerate() {
+            let left = start + bin as f64 * width;
+            x_coordinates.extend([left, left, left + width, left + width]);
+            y_coordinates.extend([0.0, *height, *height, 0.0]);
+        }
+        (x_coordinates, y_coordinates)
+    }
+
+    /// Builds the filled trace for the bins.
+    pub(crate) fn trace(&self, skin: Skin) -> Box<Scatter<f64, f64>> {
+        let (x_coordinates, y_coordinates) = self.outline();
+        let trace = Scatter::new(x_coordinates, y_coordinates)
+            .mode(Mode::Lines)
+            .fill(Fill::ToSelf)
+            .fill_color(self.design.fill(skin))
+            .line(
+                Line::new()
+                    .color(self.design.stroke(skin))
+                    .width(skin.theme.lines.outline),
+            )
+            .name(legend_name(&self.name, skin))
+            .show_legend(self.name.is_some());
+        trace.grouped(&self.legend_group, &self.name, skin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn histogram(samples: Vec<f64>, bins: Bins, normalization: Normalization) -> Histogram {
+        Histogram {
+            samples,
+            bins,
+            normalization,
+            design: RegionDesign::new(Color::Green, MAIN_COLOR_SLOT),
+            density: None,
+            name: None,
+            legend_group: None,
+        }
+    }
+
+    #[test]
+    fn bins_start_at_the_smallest_sample() {
+        let histogram = histogram(vec![2.0, 2.5, 4.0], Bins::Width(1.0), Normalization::Count);
+        let (start, width, heights) = histogram.heights();
+        assert_eq!((start, width), (2.0, 1.0));
+        assert_eq!(heights, vec![2.0, 1.0]);
+        let (x_coordinates, _) = histogram.outline();
+        assert_eq!(x_coordinates, vec![2.0, 2.0, 3.0, 3.0, 3.0, 3.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn largest_sample_falls_in_the_last_bin() {
+        let samples = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let (_, width, heights) =
+            histogram(samples, Bins::Count(4), Normalization::Count).heights();
+        assert_eq!(width, 1.0);
+        assert_eq!(heights, vec![1.0, 1.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn non_finite_samples_are_ignored() {
+        let samples = vec![f64::NAN, 0.0, 1.0, f64::INFINITY];
+        let (_, _, heights) = histogram(samples, Bins::Count(2), Normalization::Count).heights();
+        assert_eq!(heights, vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn probabilities_sum_to_one() {
+        let samples = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let (_, _, heights) =
+            histogram(samples, Bins::Count(4), Normalization::Probability).heights();
+        assert_eq!(heights, vec![0.2, 0.2, 0.2, 0.4]);
+    }
+
+    #[test]
+    fn densities_integrate_to_one() {
+        let samples = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let (_, width, heights) =
+            histogram(samples, Bins::Width(0.5), Normalization::Density).heights();
+        assert_eq!(heights.len(), 8);
+        let area = heights.iter().map(|height| height * width).sum::<f64>();
+        assert!((area - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn freedman_diaconis_falls_back_to_sturges_for_far_outliers() {
+        let mut samples = (0..1000).map(|i| i as f64 / 1000.0).collect::<Vec<f64>>();
+        samples.push(1e9);
+        let histogram = histogram(samples, Bins::FreedmanDiaconis, Normalization::Count);
+        assert!(histogram.validate().is_ok());
+        let (_, _, heights) = histogram.heights();
+        assert_eq!(heights.len(), 11);
+        assert_eq!(heights.iter().sum::<f64>(), 1001.0);
+    }
+
+    #[test]
+    fn too_many_bins_are_rejected() {
+        let narrow = histogram(vec![0.0, 1.0], Bins::Width(1e-6), Normalization::Count);
+        assert!(matches!(narrow.validate(), Err(FigureError::InvalidBins)));
+        assert!(narrow.heights().2.len() <= MAX_BINS);
+        let many = histogram(
+            vec![0.0, 1.0],
+            Bins::Count(MAX_BINS + 1),
+            Normalization::Count,
+        );
+        assert!(matches!(many.validate(), Err(FigureError::InvalidBins)));
+    }
+}
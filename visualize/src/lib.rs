@@ -1,4 +1,14 @@
+pub mod annotations;
+pub mod bars;
+pub mod candles;
+pub mod colormap;
 pub mod design;
+pub mod distributions;
+pub mod error;
+pub mod fan;
+pub mod figure;
 pub mod file_handler;
 pub mod functions;
+pub mod histogram;
 pub mod plot;
+pub mod theme;
@@ -1,14 +1,22 @@
-//! A module for plotting curves and regions.
+//! A module for plotting curves, regions and surfaces.
 
 #![warn(missing_docs)]
 use plotly::{
     color::NamedColor,
-    common::{Fill, Font, Line, Marker, Mode, Title},
-    layout::{Axis, Legend, Margin},
-    Plot, Scatter,
+    common::{ErrorData, ErrorType, Fill, Font, Line, LineShape, Marker, Mode},
+    contour::{Coloring, Contours},
+    Bar, BoxPlot, Candlestick, Contour, HeatMap, Histogram, Scatter,
 };
+use serde::Serialize;
 
-use crate::design::*;
+use crate::{
+    colormap::{color_bar, Colormap},
+    design::*,
+    error::FigureError,
+    figure::Figure,
+    functions::interpolate,
+    theme::Skin,
+};
 
 /// A struct to hold the data for a curve.
 pub struct Curve {
@@ -20,6 +28,36 @@ pub struct Curve {
     pub design: CurveDesign,
     /// An optional name for the curve that appears in the legend.
     pub name: Option<String>,
+    /// Optional uncertainty in the coordinates of the curve.
+    pub errors: Option<CurveErrors>,
+    /// An optional legend group, so the curve and its error band toggle with the traces sharing it.
+    pub legend_group: Option<String>,
+}
+
+/// The uncertainty in the coordinates of a curve.
+///
+/// Markers get error bars in both directions, while any style with a line gets a translucent band
+/// for the y errors and error bars for the x errors.
+#[derive(Clone, Debug, Default)]
+pub struct CurveErrors {
+    /// The error of every x coordinate, if any.
+    pub x: Option<ErrorValues>,
+    /// The error of every y coordinate, if any.
+    pub y: Option<ErrorValues>,
+}
+
+/// The error of every coordinate along one axis.
+#[derive(Clone, Debug)]
+pub enum ErrorValues {
+    /// The same error below and above each coordinate.
+    Symmetric(Vec<f64>),
+    /// Separate errors below and above each coordinate.
+    Asymmetric {
+        /// The error below each coordinate.
+        minus: Vec<f64>,
+        /// The error above each coordinate.
+        plus: Vec<f64>,
+    },
 }
 
 /// A struct to hold the data for a filled in region.
@@ -34,19 +72,163 @@ pub struct Region {
     pub design: RegionDesign,
     /// An optional name for the region that appears in the legend.
     pub name: Option<String>,
+    /// An optional legend group, so the region toggles with the curves it shades.
+    pub legend_group: Option<String>,
+}
+
+/// A struct to hold a grid of values over two parameters, drawn as a heatmap.
+pub struct Surface2D {
+    /// The x coordinates of the columns of the grid.
+    pub x_coordinates: Vec<f64>,
+    /// The y coordinates of the rows of the grid.
+    pub y_coordinates: Vec<f64>,
+    /// One row of values per y coordinate, each with one value per x coordinate.
+    pub z_values: Vec<Vec<f64>>,
+    /// The colormap the values are drawn from.
+    pub colormap: Colormap,
+    /// The number of labeled contour lines drawn over the heatmap, if any.
+    pub contour_lines: Option<usize>,
+    /// The title of the colorbar.
+    pub label: String,
 }
 
 /// A struct to hold the data for a the axes around curves and regions.
+#[derive(Default)]
 pub struct Axes {
     /// A string for the title of the x-axis.
     pub x_label: String,
     /// A string for the title of the y-axis.
     pub y_label: String,
     /// A tuple for the x and y limits of the plot.
+    /// An empty vector fits that axis to the data drawn against it.
     pub bounds: (Vec<f64>, Vec<f64>),
+    /// A tuple for the x and y scale types, linear by default.
+    pub scales: (Scale, Scale),
+    /// A tuple for how the x and y axes are fit when their bounds are empty.
+    pub auto_ranges: (AutoRange, AutoRange),
+    /// An optional right-hand y-axis for curves added with `secondary_curve`.
+    pub secondary_y: Option<SecondaryAxis>,
+}
+
+/// A struct to hold the label and limits of a secondary right-hand y-axis.
+#[derive(Default)]
+pub struct SecondaryAxis {
+    /// A string for the title of the secondary y-axis.
+    pub label: String,
+    /// The y limits of the secondary axis, empty to fit the secondary curves.
+    pub bounds: Vec<f64>,
+    /// The scale type of the secondary axis.
+    pub scale: Scale,
+    /// How the secondary axis is fit when its bounds are empty.
+    pub auto_range: AutoRange,
+}
+
+/// A struct to hold how an axis without bounds is fit to its data.
+#[derive(Copy, Clone, Debug)]
+pub struct AutoRange {
+    /// The fraction of the data range added on either side, in the space of the scale.
+    pub padding: f64,
+    /// Whether the range is stretched to include zero, ignored on log scales.
+    pub include_zero: bool,
+}
+
+impl Default for AutoRange {
+    fn default() -> Self {
+        Self {
+            padding: 0.05,
+            include_zero: false,
+        }
+    }
+}
+
+impl Axes {
+    /// Checks that the bounds of every log axis are finite and positive.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        let secondary = self
+            .secondary_y
+            .iter()
+            .map(|secondary| (&secondary.bounds, secondary.scale));
+        let axes = [
+            (&self.bounds.0, self.scales.0),
+            (&self.bounds.1, self.scales.1),
+        ];
+        for (bounds, scale) in axes.into_iter().chain(secondary) {
+            if let (Scale::Log, Some(bound)) = (
+                scale,
+                bounds
+                    .iter()
+                    .find(|bound| !(bound.is_finite() && **bound > 0.0)),
+            ) {
+                return Err(FigureError::LogBound(*bound));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AutoRange {
+    /// Computes padded bounds around the values, or nothing when there is no usable data.
+    ///
+    /// Log scales only consider positive values and pad in powers of ten.
+    /// Category axes are left to plotly.
+    pub fn fit(&self, values: impl Iterator<Item = f64>, scale: Scale) -> Vec<f64> {
+        let values = values.filter(|value| value.is_finite());
+        let values = match scale {
+            Scale::Category => return vec![],
+            Scale::Log => values
+                .filter(|value| *value > 0.0)
+                .map(f64::log10)
+                .collect::<Vec<f64>>(),
+            Scale::Linear | Scale::Date => values.collect::<Vec<f64>>(),
+        };
+        if values.is_empty() {
+            return vec![];
+        }
+        let mut min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let mut max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let include_zero = self.include_zero && !matches!(scale, Scale::Log);
+        if include_zero {
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+        let padding = match max - min {
+            span if span > 0.0 => self.padding * span,
+            _ => self.padding * min.abs().max(1.0),
+        };
+        // Padding at zero would push an included zero off the edge of the plot.
+        let lower = match include_zero && min == 0.0 {
+            true => min,
+            false => min - padding,
+        };
+        let upper = match include_zero && max == 0.0 {
+            true => max,
+            false => max + padding,
+        };
+        match scale {
+            Scale::Log => vec![10_f64.powf(lower), 10_f64.powf(upper)],
+            _ => vec![lower, upper],
+        }
+    }
+}
+
+/// An enum for the ways an axis can map values onto the page.
+///
+/// Bounds are always given in data units and converted to the space of the scale.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum Scale {
+    /// Evenly spaced values.
+    #[default]
+    Linear,
+    /// Evenly spaced powers of ten, bounds must be positive.
+    Log,
+    /// Values and bounds are milliseconds since the unix epoch.
+    Date,
+    /// Every distinct value is its own evenly spaced category.
+    Category,
 }
 
 /// A struct that holds high level visualization data for the plot.
+#[derive(Copy, Clone)]
 pub struct Display {
     /// A boolean to determine if the plot background should be transparent.
     pub transparent: bool,
@@ -56,7 +238,640 @@ pub struct Display {
     pub show: bool,
 }
 
+/// The static image formats a figure can be exported to.
+#[derive(Copy, Clone, Debug)]
+pub enum ImageFormat {
+    /// A raster image that keeps the transparent background.
+    Png,
+    /// A vector image that keeps the transparent background.
+    Svg,
+    /// A vector document, always drawn on a solid background.
+    Pdf,
+    /// A raster image without an alpha channel, always drawn on a solid background.
+    Jpeg,
+}
+
+impl ImageFormat {
+    /// The file extension kaleido writes for this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+            ImageFormat::Pdf => "pdf",
+            ImageFormat::Jpeg => "jpeg",
+        }
+    }
+
+    /// Whether a transparent background survives the export.
+    pub fn supports_transparency(&self) -> bool {
+        matches!(self, ImageFormat::Png | ImageFormat::Svg)
+    }
+}
+
+impl From<ImageFormat> for plotly::ImageFormat {
+    fn from(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Png => plotly::ImageFormat::PNG,
+            ImageFormat::Svg => plotly::ImageFormat::SVG,
+            ImageFormat::Pdf => plotly::ImageFormat::PDF,
+            ImageFormat::Jpeg => plotly::ImageFormat::JPEG,
+        }
+    }
+}
+
+/// A struct that holds the settings for a static image export.
+///
+/// The image has the size of the figure, see [`Figure::size`].
+#[derive(Copy, Clone, Debug)]
+pub struct ImageExport {
+    /// The format of the exported file.
+    pub format: ImageFormat,
+    /// A multiplier on the width and height, e.g. `2.0` for high density screens.
+    pub scale: f64,
+}
+
+impl Default for ImageExport {
+    fn default() -> Self {
+        Self {
+            format: ImageFormat::Png,
+            scale: 1.0,
+        }
+    }
+}
+
+impl ImageExport {
+    /// Checks that the scale is a positive number.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        match self.scale.is_finite() && self.scale > 0.0 {
+            true => Ok(()),
+            false => Err(FigureError::InvalidScale(self.scale)),
+        }
+    }
+}
+
+impl Default for Display {
+    fn default() -> Self {
+        Self {
+            transparent: true,
+            mode: DisplayMode::Dark,
+            show: false,
+        }
+    }
+}
+
+/// The opacity of the translucent band around a line with y errors and of annotation spans,
+/// the `55` alpha of the brand, lighter than the `AA` of regions so lines over it stay legible.
+pub(crate) const BAND_OPACITY: f64 = 85.0 / 255.0;
+
+/// Interpolates two curves onto the union of their x coordinates within the range they share.
+///
+/// Returns `None` if either curve is empty or has mismatched coordinates,
+/// or if the curves do not share any stretch of x coordinates.
+fn shared_grid(
+    first: (&[f64], &[f64]),
+    second: (&[f64], &[f64]),
+) -> Option<(Vec<f64>, Vec<f64>, Vec<f64>)> {
+    let well_formed = |(x, y): (&[f64], &[f64])| x.len() == y.len() && !x.is_empty();
+    if !well_formed(first) || !well_formed(second) {
+        return None;
+    }
+    let start = first.0[0].max(second.0[0]);
+    let end = first.0[first.0.len() - 1].min(second.0[second.0.len() - 1]);
+    let mut x_coordinates = first
+        .0
+        .iter()
+        .chain(second.0)
+        .cloned()
+        .filter(|x| *x >= start && *x <= end)
+        .collect::<Vec<f64>>();
+    x_coordinates.sort_by(f64::total_cmp);
+    x_coordinates.dedup();
+    if x_coordinates.len() < 2 {
+        return None;
+    }
+    let interpolated = |(x, y): (&[f64], &[f64])| {
+        x_coordinates
+            .iter()
+            .map(|at| interpolate(x, y, *at))
+            .collect::<Vec<f64>>()
+    };
+    let (first_y, second_y) = (interpolated(first), interpolated(second));
+    Some((x_coordinates, first_y, second_y))
+}
+
+/// Formats a legend entry in the text mode, leaving unnamed traces blank.
+pub(crate) fn legend_name(name: &Option<String>, skin: Skin) -> String {
+    match name {
+        Some(name) => format!(" {}", skin.typeset(name, &skin.theme.latex.legend)),
+        None => "".to_string(),
+    }
+}
+
+/// The legend group of a trace, its explicit group if it has one
+/// and otherwise its name when the legend groups traces sharing a name.
+pub(crate) fn legend_group<'a>(
+    group: &'a Option<String>,
+    name: &'a Option<String>,
+    skin: Skin,
+) -> Option<&'a str> {
+    match (group, skin.grouped_legend) {
+        (Some(group), _) => Some(group),
+        (None, true) => name.as_deref(),
+        (None, false) => None,
+    }
+}
+
+/// A plotly trace that can be put in a legend group.
+pub(crate) trait Grouped: Sized {
+    /// Puts the trace in `group`.
+    fn join(self, group: &str) -> Self;
+
+    /// Puts the trace in its [`legend_group`], if it has one.
+    fn grouped(self, group: &Option<String>, name: &Option<String>, skin: Skin) -> Self {
+        match legend_group(group, name, skin) {
+            Some(group) => self.join(group),
+            None => self,
+        }
+    }
+}
+
+macro_rules! grouped {
+    ($(<$($parameter:ident),+> $trace:ty),+ $(,)?) => {$(
+        impl<$($parameter: Serialize + Clone + 'static),+> Grouped for $trace {
+            fn join(self, group: &str) -> Self {
+                self.legend_group(group)
+            }
+        }
+    )+};
+}
+
+grouped!(
+    <X, Y> Box<Scatter<X, Y>>,
+    <X, Y> Box<Bar<X, Y>>,
+    <T, O> Candlestick<T, O>,
+    <X, Y> Box<BoxPlot<X, Y>>,
+    <H> Box<Histogram<H>>,
+);
+
+/// Checks that a color slot indexes into the ten step palette ramps.
+pub(crate) fn validate_color_slot(color: &Color, color_slot: usize) -> Result<(), FigureError> {
+    match color.ramp() {
+        Some(ramp) if color_slot >= ramp.len() => Err(FigureError::ColorSlot(color_slot)),
+        _ => Ok(()),
+    }
+}
+
+impl Region {
+    /// Fills between a curve and the x-axis.
+    pub fn to_zero(x_coordinates: &[f64], y_coordinates: &[f64], design: RegionDesign) -> Self {
+        Self::to_constant(x_coordinates, y_coordinates, 0.0, design)
+    }
+
+    /// Fills between a curve and the horizontal line at `level`.
+    pub fn to_constant(
+        x_coordinates: &[f64],
+        y_coordinates: &[f64],
+        level: f64,
+        design: RegionDesign,
+    ) -> Self {
+        Self {
+            x_coordinates: (x_coordinates.to_vec(), x_coordinates.to_vec()),
+            y_coordinates: (y_coordinates.to_vec(), vec![level; x_coordinates.len()]),
+            design,
+            name: None,
+            legend_group: None,
+        }
+    }
+
+    /// Fills between two curves with increasing x coordinates, over the x range they share.
+    ///
+    /// Both curves are linearly interpolated onto the union of their x coordinates,
+    /// so they do not need to be sampled at the same points.
+    /// Returns `None` if either curve is empty or has mismatched coordinates,
+    /// or if the curves do not share any stretch of x coordinates.
+    pub fn between(
+        first: (&[f64], &[f64]),
+        second: (&[f64], &[f64]),
+        design: RegionDesign,
+    ) -> Option<Self> {
+        let (x_coordinates, first_y, second_y) = shared_grid(first, second)?;
+        Some(Self {
+            x_coordinates: (x_coordinates.clone(), x_coordinates),
+            y_coordinates: (first_y, second_y),
+            design,
+            name: None,
+            legend_group: None,
+        })
+    }
+
+    /// Fills between two curves with increasing x coordinates wherever `upper` lies above `lower`.
+    ///
+    /// Every stretch where it does becomes its own region, cut off where the curves cross.
+    /// Name only the first region with [`Region::name`] so the legend has a single entry.
+    /// There are no regions if either curve is empty or has mismatched coordinates,
+    /// or if the curves do not share any stretch of x coordinates.
+    pub fn where_greater(
+        upper: (&[f64], &[f64]),
+        lower: (&[f64], &[f64]),
+        design: RegionDesign,
+    ) -> Vec<Self> {
+        let Some((x_coordinates, upper_y, lower_y)) = shared_grid(upper, lower) else {
+            return vec![];
+        };
+        let mut stretches = vec![];
+        let mut stretch: (Vec<f64>, Vec<f64>, Vec<f64>) = (vec![], vec![], vec![]);
+        for index in 0..x_coordinates.len() {
+            let gap = upper_y[index] - lower_y[index];
+            if gap > 0.0 {
+                stretch.0.push(x_coordinates[index]);
+                stretch.1.push(upper_y[index]);
+                stretch.2.push(lower_y[index]);
+            }
+            let Some(next) = x_coordinates.get(index + 1) else {
+                break;
+            };
+            let next_gap = upper_y[index + 1] - lower_y[index + 1];
+            if (gap > 0.0) != (next_gap > 0.0) {
+                // The curves cross between this point and the next
+                let t = gap / (gap - next_gap);
+                stretch
+                    .0
+                    .push(x_coordinates[index] + t * (next - x_coordinates[index]));
+                let crossing = upper_y[index] + t * (upper_y[index + 1] - upper_y[index]);
+                stretch.1.push(crossing);
+                stretch.2.push(crossing);
+                if gap > 0.0 {
+                    stretches.push(std::mem::take(&mut stretch));
+                }
+            }
+        }
+        stretches.push(stretch);
+        stretches
+            .into_iter()
+            .filter(|(x_coordinates, _, _)| x_coordinates.len() > 1)
+            .map(|(x_coordinates, upper_y, lower_y)| Self {
+                x_coordinates: (x_coordinates.clone(), x_coordinates),
+                y_coordinates: (upper_y, lower_y),
+                design: design.clone(),
+                name: None,
+                legend_group: None,
+            })
+            .collect()
+    }
+
+    /// Names the region in the legend.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Puts the region in a legend group, whatever its name.
+    pub fn legend_group(mut self, group: impl Into<String>) -> Self {
+        self.legend_group = Some(group.into());
+        self
+    }
+
+    /// Checks that both bounding curves are well formed.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        for (x_coordinates, y_coordinates) in [
+            (&self.x_coordinates.0, &self.y_coordinates.0),
+            (&self.x_coordinates.1, &self.y_coordinates.1),
+        ] {
+            if x_coordinates.len() != y_coordinates.len() {
+                return Err(FigureError::MismatchedCoordinates {
+                    name: self.name.clone(),
+                    x_len: x_coordinates.len(),
+                    y_len: y_coordinates.len(),
+                });
+            }
+        }
+        validate_color_slot(&self.design.color, self.design.color_slot)
+    }
+
+    /// Builds the filled scatter trace for this region.
+    pub(crate) fn trace(&self, skin: Skin) -> Box<Scatter<f64, f64>> {
+        let color = self.design.fill(skin);
+        // Combine the two x coordinates for the bounding curves by reversing the second and appending into a longer vector.
+        let x_combined = self
+            .x_coordinates
+            .0
+            .iter()
+            .chain(self.x_coordinates.1.iter().rev())
+            .cloned()
+            .collect::<Vec<f64>>();
+        // Combine the two y coordinates for the bounding curves in the same way.
+        let y_combined = self
+            .y_coordinates
+            .0
+            .iter()
+            .chain(self.y_coordinates.1.iter().rev())
+            .cloned()
+            .collect::<Vec<f64>>();
+
+        let trace = Scatter::new(x_combined, y_combined)
+            .fill(Fill::ToSelf)
+            .fill_color(color)
+            .line(Line::new().color(NamedColor::Transparent))
+            .name(legend_name(&self.name, skin))
+            .show_legend(self.name.is_some());
+        trace.grouped(&self.legend_group, &self.name, skin)
+    }
+}
+
+impl ErrorValues {
+    /// The coordinates shifted down and up by their errors.
+    fn bounds(&self, values: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        let (minus, plus) = match self {
+            ErrorValues::Symmetric(errors) => (errors, errors),
+            ErrorValues::Asymmetric { minus, plus } => (minus, plus),
+        };
+        (
+            values
+                .iter()
+                .zip(minus)
+                .map(|(value, error)| value - error)
+                .collect(),
+            values
+                .iter()
+                .zip(plus)
+                .map(|(value, error)| value + error)
+                .collect(),
+        )
+    }
+
+    /// Checks that there is an error below and above every coordinate.
+    fn validate(&self, name: &Option<String>, values: usize) -> Result<(), FigureError> {
+        let lengths = match self {
+            ErrorValues::Symmetric(errors) => [errors.len(), errors.len()],
+            ErrorValues::Asymmetric { minus, plus } => [minus.len(), plus.len()],
+        };
+        match lengths.iter().find(|errors| **errors != values) {
+            Some(errors) => Err(FigureError::MismatchedErrors {
+                name: name.clone(),
+                values,
+                errors: *errors,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Builds plotly error bars in the given color.
+    fn error_data(&self, color: &str, skin: Skin) -> ErrorData {
+        let error_data = ErrorData::new(ErrorType::Data)
+            .color(color.to_string())
+            .thickness(skin.theme.lines.light);
+        match self {
+            ErrorValues::Symmetric(errors) => error_data.array(errors.clone()),
+            ErrorValues::Asymmetric { minus, plus } => error_data
+                .symmetric(false)
+                .array(plus.clone())
+                .array_minus(minus.clone()),
+        }
+    }
+}
+
+impl Curve {
+    /// Creates an unnamed curve without errors, see [`Curve::name`] and [`Curve::errors`].
+    pub fn new(x_coordinates: Vec<f64>, y_coordinates: Vec<f64>, design: CurveDesign) -> Self {
+        Self {
+            x_coordinates,
+            y_coordinates,
+            design,
+            name: None,
+            errors: None,
+            legend_group: None,
+        }
+    }
+
+    /// Sets the name of the curve in the legend.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the uncertainty in the coordinates of the curve.
+    pub fn errors(mut self, errors: CurveErrors) -> Self {
+        self.errors = Some(errors);
+        self
+    }
+
+    /// Puts the curve in a legend group, whatever its name.
+    pub fn legend_group(mut self, group: impl Into<String>) -> Self {
+        self.legend_group = Some(group.into());
+        self
+    }
+
+    /// Checks that the coordinates and errors pair up and the color exists.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        if self.x_coordinates.len() != self.y_coordinates.len() {
+            return Err(FigureError::MismatchedCoordinates {
+                name: self.name.clone(),
+                x_len: self.x_coordinates.len(),
+                y_len: self.y_coordinates.len(),
+            });
+        }
+        if let Some(errors) = &self.errors {
+            for error_values in errors.x.iter().chain(errors.y.iter()) {
+                error_values.validate(&self.name, self.x_coordinates.len())?;
+            }
+        }
+        validate_color_slot(&self.design.color, self.design.color_slot)
+    }
+
+    /// Every x coordinate the curve and its errors reach.
+    pub(crate) fn x_values(&self) -> Vec<f64> {
+        Self::extents(
+            &self.x_coordinates,
+            self.errors.as_ref().and_then(|e| e.x.as_ref()),
+        )
+    }
+
+    /// Every y coordinate the curve and its errors reach.
+    pub(crate) fn y_values(&self) -> Vec<f64> {
+        Self::extents(
+            &self.y_coordinates,
+            self.errors.as_ref().and_then(|e| e.y.as_ref()),
+        )
+    }
+
+    /// The coordinates along with their lower and upper bounds when they have errors.
+    fn extents(values: &[f64], errors: Option<&ErrorValues>) -> Vec<f64> {
+        match errors {
+            Some(errors) => {
+                let (lower, upper) = errors.bounds(values);
+                values.iter().cloned().chain(lower).chain(upper).collect()
+            }
+            None => values.to_vec(),
+        }
+    }
+
+    /// Builds the translucent band of the y errors of a line in the given color, if it has one.
+    fn band(&self, color: &str, skin: Skin) -> Option<Box<Scatter<f64, f64>>> {
+        self.design.style.line()?;
+        let (lower, upper) = self
+            .errors
+            .as_ref()?
+            .y
+            .as_ref()?
+            .bounds(&self.y_coordinates);
+        let x_combined = self
+            .x_coordinates
+            .iter()
+            .chain(self.x_coordinates.iter().rev())
+            .cloned()
+            .collect::<Vec<f64>>();
+        let y_combined = upper
+            .into_iter()
+            .chain(lower.into_iter().rev())
+            .collect::<Vec<f64>>();
+        let band = Scatter::new(x_combined, y_combined)
+            .mode(Mode::Lines)
+            .fill(Fill::ToSelf)
+            .fill_color(with_opacity(color, BAND_OPACITY))
+            .line(Line::new().color(NamedColor::Transparent))
+            .hover_info(plotly::common::HoverInfo::Skip)
+            .show_legend(false);
+        Some(band.grouped(&self.legend_group, &self.name, skin))
+    }
+
+    /// Builds the error band of this curve, if it has one, followed by its line or markers.
+    pub(crate) fn traces(&self, skin: Skin) -> impl Iterator<Item = Box<Scatter<f64, f64>>> {
+        self.traces_with_color(&self.design.stroke(skin), skin)
+    }
+
+    /// Builds the traces for this curve in the given color rather than its design color.
+    pub(crate) fn traces_with_color(
+        &self,
+        color: &str,
+        skin: Skin,
+    ) -> impl Iterator<Item = Box<Scatter<f64, f64>>> {
+        self.band(color, skin)
+            .into_iter()
+            .chain(std::iter::once(self.trace_with_color(color, skin)))
+    }
+
+    /// Builds the line or marker scatter trace for this curve in the given color.
+    fn trace_with_color(&self, color: &str, skin: Skin) -> Box<Scatter<f64, f64>> {
+        let trace = Scatter::new(self.x_coordinates.clone(), self.y_coordinates.clone());
+        let (line, marker) = (self.design.style.line(), self.design.style.marker());
+        let has_line = line.is_some();
+        let trace = match (has_line, marker.is_some()) {
+            (true, true) => trace.mode(Mode::LinesMarkers),
+            (false, true) => trace.mode(Mode::Markers),
+            _ => trace.mode(Mode::Lines),
+        };
+        let trace = match line {
+            Some((line_emphasis, shape)) => {
+                let line = Line::new()
+                    .color(color.to_string())
+                    .width(skin.line_width(line_emphasis));
+                let line = match line_emphasis.dash() {
+                    Some(dash) => line.dash(dash),
+                    None => line,
+                };
+                let line = match shape {
+                    LineShape::Linear => line,
+                    shape => line.shape(shape),
+                };
+                trace.line(line)
+            }
+            None => trace,
+        };
+        let trace = match marker {
+            Some((marker_emphasis, shape)) => {
+                let marker = Marker::new()
+                    .color(color.to_string())
+                    .size(skin.marker_size(marker_emphasis));
+                let marker = match shape {
+                    MarkerShape::Circle => marker,
+                    shape => marker.symbol(shape.symbol()),
+                };
+                trace.marker(marker)
+            }
+            None => trace,
+        };
+        let errors = self.errors.clone().unwrap_or_default();
+        let trace = match errors.x {
+            Some(x_errors) => trace.error_x(x_errors.error_data(color, skin)),
+            None => trace,
+        };
+        let trace = match (errors.y, has_line) {
+            (Some(y_errors), false) => trace.error_y(y_errors.error_data(color, skin)),
+            _ => trace,
+        };
+        let trace = trace
+            .name(legend_name(&self.name, skin))
+            .show_legend(self.name.is_some());
+        trace.grouped(&self.legend_group, &self.name, skin)
+    }
+}
+
+impl Surface2D {
+    /// Checks that the grid of values matches the coordinates.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        let mismatched = self
+            .z_values
+            .iter()
+            .find(|row| row.len() != self.x_coordinates.len());
+        if self.z_values.len() != self.y_coordinates.len() || mismatched.is_some() {
+            return Err(FigureError::MismatchedSurface {
+                x_len: self.x_coordinates.len(),
+                y_len: self.y_coordinates.len(),
+                rows: self.z_values.len(),
+                columns: mismatched
+                    .or(self.z_values.first())
+                    .map_or(0, |row| row.len()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Builds the heatmap trace with its colorbar placed `x` across the figure.
+    pub(crate) fn heat_map(&self, skin: Skin, x: f64) -> Box<HeatMap<f64, f64, Vec<f64>>> {
+        HeatMap::new(
+            self.x_coordinates.clone(),
+            self.y_coordinates.clone(),
+            self.z_values.clone(),
+        )
+        .color_scale(self.colormap.color_scale(skin))
+        .color_bar(color_bar(&self.label, skin, x))
+        .show_legend(false)
+    }
+
+    /// Builds the labeled contour lines drawn over the heatmap, if any were asked for.
+    pub(crate) fn contour(&self, skin: Skin) -> Option<Box<Contour<Vec<f64>>>> {
+        let contour_lines = self.contour_lines?;
+        let contours = Contours::new()
+            .coloring(Coloring::None)
+            .show_labels(true)
+            .label_font(
+                Font::new()
+                    .size(skin.theme.fonts.contour_label)
+                    .color(skin.foreground()),
+            );
+        Some(
+            Contour::new(
+                self.x_coordinates.clone(),
+                self.y_coordinates.clone(),
+                self.z_values.clone(),
+            )
+            .auto_contour(true)
+            .n_contours(contour_lines)
+            .contours(contours)
+            .line(
+                Line::new()
+                    .color(skin.foreground())
+                    .width(skin.theme.lines.outline),
+            )
+            .show_scale(false)
+            .show_legend(false),
+        )
+    }
+}
+
 /// The main plotting function for curves and regions.
+///
+/// Prefer [`Figure`], which this wraps, so new options do not change every call site.
 pub fn transparent_plot(
     curves: Option<Vec<Curve>>,
     regions: Option<Vec<Region>>,
@@ -65,194 +880,263 @@ pub fn transparent_plot(
     display: Display,
     file_name: Option<String>,
 ) {
-    let mut plot = Plot::new();
-    // TODO: Below should be put into a helper function
-
-    if let Some(regions) = regions {
-        for region in regions.iter() {
-            let color_slot = region.design.color_slot;
-            let color = match region.design.color {
-                Color::Green => format!("{}AA", PRIMITIVE_GREENS[color_slot]),
-                Color::Blue => format!("{}AA", PRIMITIVE_BLUES[color_slot]),
-                Color::Purple => format!("{}AA", PRIMITIVE_PURPLES[color_slot]),
-                Color::Grey => format!("{}AA", PRIMITIVE_GREYS[color_slot]),
-                Color::Black => format!("{}AA", PRIMITIVE_BLACK),
-                Color::White => format!("{}AA", PRIMITIVE_WHITE),
-            };
-            // Combine the two x coordinates for the bounding curves by reversing the second and appending into a longer vector.
-            let x1_coords = region.x_coordinates.0.clone();
-            let x2_coords = region.x_coordinates.1.clone();
-            let x2_reversed = x2_coords.iter().cloned().rev().collect::<Vec<f64>>();
-            // Append thing to x1_coords to create a new vector
-            let x_combined = x1_coords
-                .into_iter()
-                .chain(x2_reversed.into_iter())
-                .collect::<Vec<f64>>();
-
-            // Combine the two y coordinates for the bounding curves by reversing the second and appending into a longer vector.
-            let y1_coords = region.y_coordinates.0.clone();
-            let y2_coords = region.y_coordinates.1.clone();
-            let y2_reversed = y2_coords.iter().cloned().rev().collect::<Vec<f64>>();
-            // Append thing to x1_coords to create a new vector
-            let y_combined = y1_coords
-                .into_iter()
-                .chain(y2_reversed.into_iter())
-                .collect::<Vec<f64>>();
-
-            let trace = Scatter::new(x_combined, y_combined)
-                .fill(Fill::ToSelf)
-                .fill_color(color)
-                .line(Line::new().color(NamedColor::Transparent))
-                .name(&match region.name.clone() {
-                    Some(name) => format!(" {} {} {}", "$\\Large{", name, "}$"),
-                    None => "".to_string(),
-                })
-                .show_legend(region.name.is_some());
-            plot.add_trace(trace);
+    let figure = Figure::new()
+        .curves(curves.unwrap_or_default())
+        .regions(regions.unwrap_or_default())
+        .axes(axes)
+        .title(title)
+        .display(display);
+    let figure = match file_name {
+        Some(file_name) => figure.save_as(file_name),
+        None => figure,
+    };
+    figure.plot();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::{TextMode, Theme};
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-9,
+                "{} != {}",
+                actual,
+                expected
+            );
         }
     }
-    if let Some(curves) = curves {
-        for curve in curves.iter() {
-            let color_slot = curve.design.color_slot;
-            let trace = match &curve.design.style {
-                Style::Lines(line_emphasis) => {
-                    let line = match curve.design.color {
-                        Color::Green => Line::new().color(PRIMITIVE_GREENS[color_slot]),
-                        Color::Blue => Line::new().color(PRIMITIVE_BLUES[color_slot]),
-                        Color::Purple => Line::new().color(PRIMITIVE_PURPLES[color_slot]),
-                        Color::Grey => Line::new().color(PRIMITIVE_GREYS[color_slot]),
-                        Color::Black => Line::new().color(PRIMITIVE_BLACK),
-                        Color::White => Line::new().color(PRIMITIVE_WHITE),
-                    };
-                    let line = match line_emphasis {
-                        LineEmphasis::Light => line.width(2.0),
-                        LineEmphasis::Heavy => line.width(4.0),
-                        LineEmphasis::Dashed => {
-                            line.width(2.0).dash(plotly::common::DashType::Dash)
-                        }
-                    };
-                    Scatter::new(curve.x_coordinates.clone(), curve.y_coordinates.clone())
-                        .mode(Mode::Lines)
-                        .line(line)
-                        .name(&match curve.name.clone() {
-                            Some(name) => format!(" {} {} {}", "$\\Large{", name, "}$"),
-                            None => "".to_string(),
-                        })
-                        .show_legend(curve.name.is_some())
-                }
-                Style::Markers(marker_emphasis) => {
-                    let marker = match curve.design.color {
-                        Color::Green => Marker::new().color(PRIMITIVE_GREENS[color_slot]),
-                        Color::Blue => Marker::new().color(PRIMITIVE_BLUES[color_slot]),
-                        Color::Purple => Marker::new().color(PRIMITIVE_PURPLES[color_slot]),
-                        Color::Grey => Marker::new().color(PRIMITIVE_GREYS[color_slot]),
-                        Color::Black => Marker::new().color(PRIMITIVE_BLACK),
-                        Color::White => Marker::new().color(PRIMITIVE_WHITE),
-                    };
-                    let marker = match marker_emphasis {
-                        MarkerEmphasis::Light => marker.size(10),
-                        MarkerEmphasis::Heavy => marker.size(20),
-                    };
-                    Scatter::new(curve.x_coordinates.clone(), curve.y_coordinates.clone())
-                        .mode(Mode::Markers)
-                        .marker(marker)
-                        .name(&match curve.name.clone() {
-                            Some(name) => format!(" {} {} {}", "$\\Large{", name, "}$"),
-                            None => "".to_string(),
-                        })
-                        .show_legend(curve.name.is_some())
-                }
-            };
-            plot.add_trace(trace);
+
+    #[test]
+    fn fit_pads_both_sides() {
+        let bounds = AutoRange::default().fit([1.0, 3.0].into_iter(), Scale::Linear);
+        assert_close(&bounds, &[0.9, 3.1]);
+    }
+
+    #[test]
+    fn fit_includes_zero_without_padding_it() {
+        let auto_range = AutoRange {
+            padding: 0.05,
+            include_zero: true,
+        };
+        let positive = auto_range.fit([1.0, 3.0].into_iter(), Scale::Linear);
+        assert_close(&positive, &[0.0, 3.15]);
+        let negative = auto_range.fit([-2.0, -1.0].into_iter(), Scale::Linear);
+        assert_close(&negative, &[-2.1, 0.0]);
+    }
+
+    #[test]
+    fn fit_pads_a_single_value_by_its_magnitude() {
+        let bounds = AutoRange::default().fit([5.0].into_iter(), Scale::Linear);
+        assert_close(&bounds, &[4.75, 5.25]);
+    }
+
+    #[test]
+    fn fit_pads_log_scales_in_decades_and_ignores_zero() {
+        let auto_range = AutoRange {
+            padding: 0.05,
+            include_zero: true,
+        };
+        let bounds = auto_range.fit([0.0, 1.0, 100.0].into_iter(), Scale::Log);
+        assert_close(&bounds, &[10_f64.powf(-0.1), 10_f64.powf(2.1)]);
+    }
+
+    #[test]
+    fn fit_skips_non_finite_values() {
+        let values = [f64::NAN, 1.0, f64::INFINITY, 3.0];
+        let bounds = AutoRange::default().fit(values.into_iter(), Scale::Linear);
+        assert_close(&bounds, &[0.9, 3.1]);
+    }
+
+    #[test]
+    fn fit_leaves_categories_and_missing_data_to_plotly() {
+        let auto_range = AutoRange::default();
+        assert!(auto_range
+            .fit([1.0, 2.0].into_iter(), Scale::Category)
+            .is_empty());
+        assert!(auto_range
+            .fit([f64::NAN].into_iter(), Scale::Linear)
+            .is_empty());
+        assert!(auto_range.fit([-1.0].into_iter(), Scale::Log).is_empty());
+    }
+
+    fn design() -> RegionDesign {
+        RegionDesign::new(Color::Green, MAIN_COLOR_SLOT)
+    }
+
+    #[test]
+    fn where_greater_cuts_off_crossings_at_the_first_and_last_points() {
+        let x = [0.0, 1.0, 2.0, 3.0];
+        let regions =
+            Region::where_greater((&x, &[-1.0, 1.0, 1.0, -1.0]), (&x, &[0.0; 4]), design());
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].x_coordinates.0, vec![0.5, 1.0, 2.0, 2.5]);
+        assert_eq!(regions[0].y_coordinates.0, vec![0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(regions[0].y_coordinates.1, vec![0.0; 4]);
+    }
+
+    #[test]
+    fn where_greater_starts_and_ends_at_exact_zero_gaps() {
+        let x = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let regions =
+            Region::where_greater((&x, &[0.0, 1.0, 0.0, 0.0, 2.0]), (&x, &[0.0; 5]), design());
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].x_coordinates.0, vec![0.0, 1.0, 2.0]);
+        assert_eq!(regions[0].y_coordinates.0, vec![0.0, 1.0, 0.0]);
+        assert_eq!(regions[1].x_coordinates.0, vec![3.0, 4.0]);
+        assert_eq!(regions[1].y_coordinates.0, vec![0.0, 2.0]);
+        assert!(regions.iter().all(|region| region.name.is_none()));
+        let equal = Region::where_greater((&x, &[1.0; 5]), (&x, &[1.0; 5]), design());
+        assert!(equal.is_empty());
+    }
+
+    #[test]
+    fn between_interpolates_onto_the_shared_range() {
+        let region = Region::between(
+            (&[0.0, 2.0, 4.0], &[0.0, 2.0, 4.0]),
+            (&[1.0, 3.0, 5.0], &[1.0; 3]),
+            design(),
+        )
+        .unwrap();
+        assert_eq!(region.x_coordinates.0, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(region.y_coordinates.0, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(region.y_coordinates.1, vec![1.0; 4]);
+    }
+
+    #[test]
+    fn curves_without_a_shared_range_have_no_region() {
+        let first = (&[0.0, 1.0][..], &[1.0, 1.0][..]);
+        let second = (&[2.0, 3.0][..], &[0.0, 0.0][..]);
+        assert!(Region::between(first, second, design()).is_none());
+        assert!(Region::where_greater(first, second, design()).is_empty());
+        let mismatched = (&[0.0, 1.0][..], &[1.0][..]);
+        assert!(Region::between(mismatched, second, design()).is_none());
+    }
+
+    fn skin(theme: &Theme) -> Skin<'_> {
+        Skin {
+            theme,
+            mode: DisplayMode::Light,
+            text: TextMode::Raw,
+            grouped_legend: false,
         }
     }
 
-    let x_label = format!("{} {} {}", "$\\LARGE{", axes.x_label, "}$");
-    let x_axis = Axis::new()
-        .title(Title::new(&x_label).font(Font::new().size(24)))
-        .show_grid(true)
-        .grid_color(PRIMITIVE_GREYS[MAIN_COLOR_SLOT])
-        .zero_line(false)
-        .color(PRIMITIVE_WHITE)
-        .line_color(PRIMITIVE_WHITE)
-        .tick_prefix("$\\LARGE{")
-        .tick_suffix("}$")
-        .tick_font(Font::new().size(24))
-        .auto_margin(false)
-        .range(axes.bounds.0)
-        .ticks(plotly::layout::TicksDirection::Outside);
-    let y_label = format!("{} {} {}", "$\\LARGE{", axes.y_label, "}$");
-    let y_axis = Axis::new()
-        .title(Title::new(&y_label).font(Font::new().size(60)))
-        .show_grid(true)
-        .grid_color(PRIMITIVE_GREYS[MAIN_COLOR_SLOT])
-        .zero_line(false)
-        .show_line(true)
-        .tick_prefix("$\\LARGE{")
-        .tick_suffix("}$")
-        .tick_font(Font::new().size(24))
-        .auto_margin(false)
-        .range(axes.bounds.1)
-        .ticks(plotly::layout::TicksDirection::Outside);
-
-    let (x_axis, y_axis) = match display.mode {
-        DisplayMode::Light => (
-            x_axis.color(PRIMITIVE_BLACK).line_color(PRIMITIVE_BLACK),
-            y_axis.color(PRIMITIVE_BLACK).line_color(PRIMITIVE_BLACK),
-        ),
-        DisplayMode::Dark => (
-            x_axis.color(PRIMITIVE_WHITE).line_color(PRIMITIVE_WHITE),
-            y_axis.color(PRIMITIVE_WHITE).line_color(PRIMITIVE_WHITE),
-        ),
-    };
-    let title = format!("{} {} {}", "$\\huge{", title, "}$");
-    let layout = plotly::Layout::new()
-        .title(plotly::common::Title::new(title.as_str()))
-        .x_axis(x_axis)
-        .y_axis(y_axis)
-        .width(1200)
-        .height(900)
-        .margin(Margin::new().bottom(100).left(180).top(100).right(100));
-    let layout = match display.transparent {
-        true => layout
-            .plot_background_color("rgba(0,0,0,0)")
-            .paper_background_color("rgba(0,0,0,0)"),
-        false => match display.mode {
-            DisplayMode::Dark => layout
-                .plot_background_color(PRIMITIVE_BLACK)
-                .paper_background_color(PRIMITIVE_BLACK),
-            DisplayMode::Light => layout
-                .plot_background_color(PRIMITIVE_WHITE)
-                .paper_background_color(PRIMITIVE_WHITE),
-        },
-    };
-    let layout = match display.mode {
-        DisplayMode::Dark => layout
-            .show_legend(true)
-            .legend(
-                Legend::new()
-                    .font(Font::new().color(PRIMITIVE_WHITE).size(24))
-                    .x_anchor(plotly::common::Anchor::Auto)
-                    .y_anchor(plotly::common::Anchor::Auto),
-            )
-            .font(Font::new().color(PRIMITIVE_WHITE)),
-        DisplayMode::Light => layout
-            .legend(
-                Legend::new()
-                    .font(Font::new().color(PRIMITIVE_BLACK).size(24))
-                    .x_anchor(plotly::common::Anchor::Auto)
-                    .y_anchor(plotly::common::Anchor::Auto),
-            )
-            .font(Font::new().color(PRIMITIVE_BLACK)),
-    };
+    fn curve_traces(curve: &Curve, skin: Skin) -> Vec<serde_json::Value> {
+        curve
+            .traces(skin)
+            .map(|trace| serde_json::to_value(trace).unwrap())
+            .collect()
+    }
 
-    plot.set_layout(layout);
-    let file = match file_name {
-        Some(file_name) => file_name,
-        None => "plot.html".to_string(),
-    };
-    plot.write_html(file);
-    if display.show {
-        plot.show();
+    fn errors(x: Option<ErrorValues>, y: Option<ErrorValues>) -> CurveErrors {
+        CurveErrors { x, y }
+    }
+
+    #[test]
+    fn lines_get_a_translucent_band_for_y_errors() {
+        let theme = Theme::default();
+        let design = CurveDesign::new(Color::Blue, 3, Style::Lines(LineEmphasis::Light))
+            .opacity(Opacity::new(0.5).unwrap());
+        let curve = Curve::new(vec![0.0, 1.0], vec![1.0, 2.0], design.clone()).errors(errors(
+            Some(ErrorValues::Symmetric(vec![0.1, 0.1])),
+            Some(ErrorValues::Symmetric(vec![0.5, 1.0])),
+        ));
+        let traces = curve_traces(&curve, skin(&theme));
+        assert_eq!(traces.len(), 2);
+        let (band, line) = (&traces[0], &traces[1]);
+        assert_eq!(band["fill"], "toself");
+        assert_eq!(band["showlegend"], false);
+        assert_eq!(band["x"], serde_json::json!([0.0, 1.0, 1.0, 0.0]));
+        assert_eq!(band["y"], serde_json::json!([1.5, 3.0, 1.0, 0.5]));
+        let stroke = design.stroke(skin(&theme));
+        assert_eq!(stroke.len(), 8);
+        assert_eq!(band["fillcolor"], with_opacity(&stroke, BAND_OPACITY));
+        assert_eq!(line["line"]["color"], stroke);
+        assert!(line["error_y"].is_null());
+        assert_eq!(line["error_x"]["array"], serde_json::json!([0.1, 0.1]));
+    }
+
+    #[test]
+    fn markers_get_error_bars_for_y_errors() {
+        let theme = Theme::default();
+        let design = CurveDesign::new(Color::Green, 5, Style::Markers(MarkerEmphasis::Light));
+        let curve = Curve::new(vec![0.0, 1.0], vec![1.0, 2.0], design).errors(errors(
+            None,
+            Some(ErrorValues::Asymmetric {
+                minus: vec![0.25, 0.5],
+                plus: vec![1.0, 2.0],
+            }),
+        ));
+        let traces = curve_traces(&curve, skin(&theme));
+        assert_eq!(traces.len(), 1);
+        let error_y = &traces[0]["error_y"];
+        assert_eq!(error_y["type"], "data");
+        assert_eq!(error_y["symmetric"], false);
+        assert_eq!(error_y["array"], serde_json::json!([1.0, 2.0]));
+        assert_eq!(error_y["arrayminus"], serde_json::json!([0.25, 0.5]));
+        assert_close(&curve.y_values(), &[1.0, 2.0, 0.75, 1.5, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn errors_must_match_the_coordinates() {
+        let design = CurveDesign::new(Color::Green, 5, Style::Markers(MarkerEmphasis::Light));
+        let curve = Curve::new(vec![0.0, 1.0], vec![1.0, 2.0], design)
+            .name("short")
+            .errors(errors(
+                None,
+                Some(ErrorValues::Asymmetric {
+                    minus: vec![0.25, 0.5],
+                    plus: vec![1.0],
+                }),
+            ));
+        assert!(matches!(
+            curve.validate(),
+            Err(FigureError::MismatchedErrors { name: Some(name), values: 2, errors: 1 })
+                if name == "short"
+        ));
+    }
+
+    #[test]
+    fn styles_map_to_plotly_modes_shapes_dashes_and_symbols() {
+        let theme = Theme::default();
+        let trace = |style: Style| {
+            let design = CurveDesign::new(Color::Green, 5, style);
+            let curve = Curve::new(vec![0.0, 1.0], vec![0.0, 1.0], design);
+            curve_traces(&curve, skin(&theme)).remove(0)
+        };
+        let (light, heavy) = (theme.lines.light, theme.lines.heavy);
+        let lines = trace(Style::Lines(LineEmphasis::Light));
+        assert_eq!(lines["mode"], "lines");
+        assert_eq!(lines["line"]["width"], light);
+        assert!(lines["line"]["shape"].is_null() && lines["line"]["dash"].is_null());
+        assert!(lines["marker"].is_null());
+        let steps = trace(Style::Steps(LineEmphasis::Heavy));
+        assert_eq!(steps["line"]["shape"], "hv");
+        assert_eq!(steps["line"]["width"], heavy);
+        let splines = trace(Style::Splines(LineEmphasis::Dotted));
+        assert_eq!(splines["line"]["shape"], "spline");
+        assert_eq!(splines["line"]["dash"], "dot");
+        let markers = trace(Style::Markers(MarkerEmphasis::Light));
+        assert_eq!(markers["mode"], "markers");
+        assert_eq!(markers["marker"]["size"], theme.markers.light);
+        assert!(markers["marker"]["symbol"].is_null() && markers["line"].is_null());
+        let shaped = trace(Style::ShapedMarkers(
+            MarkerEmphasis::Heavy,
+            MarkerShape::Diamond,
+        ));
+        assert_eq!(shaped["marker"]["symbol"], "diamond");
+        assert_eq!(shaped["marker"]["size"], theme.markers.heavy);
+        let both = trace(Style::LinesAndMarkers(
+            LineEmphasis::DashDot,
+            MarkerEmphasis::Light,
+            MarkerShape::Square,
+        ));
+        assert_eq!(both["mode"], "lines+markers");
+        assert_eq!(both["line"]["dash"], "dashdot");
+        assert_eq!(both["marker"]["symbol"], "square");
     }
 }
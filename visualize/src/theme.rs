@@ -0,0 +1,628 @@
+//! Themes holding the palette, font sizes, line widths and layout of a figure.
+//!
+//! The default theme is the Primitive look. Other themes can be loaded from TOML or JSON files,
+//! where every section and field that is left out keeps its default.
+
+#![warn(missing_docs)]
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{design::*, error::FigureError};
+
+/// Everything about the look of a figure that does not depend on its data.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    /// The colors the semantic colors resolve to.
+    pub palette: Palette,
+    /// The sizes of text.
+    pub fonts: FontSizes,
+    /// The widths of lines.
+    pub lines: LineWidths,
+    /// The sizes of markers.
+    pub markers: MarkerSizes,
+    /// The size and margins of the figure.
+    pub layout: LayoutSizes,
+    /// The LaTeX sizing commands wrapped around text rendered as LaTeX.
+    pub latex: LatexSizes,
+}
+
+/// The ramps and neutrals the semantic colors resolve to, as six digit hex strings.
+///
+/// Ramps run from light to dark, the same way as in dark mode.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    /// The ramp behind [`Color::Green`].
+    pub greens: [String; 10],
+    /// The ramp behind [`Color::Blue`].
+    pub blues: [String; 10],
+    /// The ramp behind [`Color::Purple`].
+    pub purples: [String; 10],
+    /// The ramp behind [`Color::Grey`].
+    pub greys: [String; 10],
+    /// The color of [`Color::Black`], and of text in light mode.
+    pub black: String,
+    /// The color of [`Color::White`], and of text in dark mode.
+    pub white: String,
+}
+
+/// The font sizes of the figure in points.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontSizes {
+    /// The title of the x-axis.
+    pub x_label: usize,
+    /// The title of the y-axis.
+    pub y_label: usize,
+    /// The tick labels of every axis and colorbar.
+    pub ticks: usize,
+    /// The entries of the legend.
+    pub legend: usize,
+    /// The title of a colorbar.
+    pub color_bar: usize,
+    /// The text of annotations.
+    pub annotation: usize,
+    /// The labels on contour lines.
+    pub contour_label: usize,
+}
+
+/// The widths of lines in pixels.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LineWidths {
+    /// Light, dashed, dotted and dash-dot lines, error bars and arrows.
+    pub light: f64,
+    /// Heavy lines.
+    pub heavy: f64,
+    /// Outlines of histograms and contour lines.
+    pub outline: f64,
+}
+
+/// The sizes of markers in pixels.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkerSizes {
+    /// Light markers.
+    pub light: usize,
+    /// Heavy markers.
+    pub heavy: usize,
+}
+
+/// The size and margins of the figure in pixels.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutSizes {
+    /// The width of the figure.
+    pub width: usize,
+    /// The height of the figure.
+    pub height: usize,
+    /// The margin above the plot, holding the title.
+    pub top_margin: usize,
+    /// The margin below the plot, holding the x-axis title.
+    pub bottom_margin: usize,
+    /// The margin left of the plot, holding the y-axis title.
+    pub left_margin: usize,
+    /// The margin right of the plot.
+    pub right_margin: usize,
+    /// The extra right margin taken by a secondary y-axis.
+    pub secondary_axis_margin: usize,
+    /// The extra right margin taken by every colorbar.
+    pub color_bar_margin: usize,
+    /// The extra right or bottom margin taken by a legend outside of the plot.
+    pub legend_margin: usize,
+}
+
+/// The LaTeX sizing commands, without their backslash, that text rendered as LaTeX is wrapped in.
+///
+/// Each must be one of the standard sizes from `tiny` to `Huge`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LatexSizes {
+    /// The title of the figure.
+    pub title: String,
+    /// The titles of axes and colorbars.
+    pub axis_label: String,
+    /// The tick labels of axes.
+    pub ticks: String,
+    /// The tick labels of colorbars.
+    pub color_bar_ticks: String,
+    /// The entries of the legend.
+    pub legend: String,
+    /// The text of annotations.
+    pub annotation: String,
+}
+
+/// The LaTeX sizing commands from smallest to largest.
+const LATEX_SIZES: [&str; 10] = [
+    "tiny",
+    "scriptsize",
+    "footnotesize",
+    "small",
+    "normalsize",
+    "large",
+    "Large",
+    "LARGE",
+    "huge",
+    "Huge",
+];
+
+/// How titles, labels, ticks and legend names are rendered.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum TextMode {
+    /// Every text is wrapped in `$\size{...}$` and rendered by MathJax.
+    #[default]
+    Latex,
+    /// Text is shown as given, which needs no MathJax for html or static export.
+    Raw,
+    /// Text with a backslash, `^{` or `_{` is rendered as LaTeX and everything else as given,
+    /// so plain names like `file_name` stay plain. Text that already holds a `$` is passed
+    /// through untouched.
+    Auto,
+}
+
+/// The pixel size of a figure, either a preset for a common medium or free dimensions.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum FigureSize {
+    /// The Primitive default of 1200 by 900.
+    #[default]
+    Standard,
+    /// A 16:9 presentation slide of 1920 by 1080.
+    Slide,
+    /// A figure spanning one column of a two-column paper, 3.5 inches wide at 300 dpi.
+    SingleColumn,
+    /// A figure spanning both columns of a two-column paper, 7 inches wide at 300 dpi.
+    TwoColumn,
+    /// A Twitter summary card with a large image, 1200 by 628.
+    TwitterCard,
+    /// A square of 1080 by 1080, for Instagram and the like.
+    Square,
+    /// Any width and height in pixels.
+    Custom {
+        /// The width of the figure.
+        width: usize,
+        /// The height of the figure.
+        height: usize,
+    },
+}
+
+impl FigureSize {
+    /// The width and height of the figure in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        match self {
+            FigureSize::Standard => (1200, 900),
+            FigureSize::Slide => (1920, 1080),
+            FigureSize::SingleColumn => (1050, 788),
+            FigureSize::TwoColumn => (2100, 1050),
+            FigureSize::TwitterCard => (1200, 628),
+            FigureSize::Square => (1080, 1080),
+            FigureSize::Custom { width, height } => (*width, *height),
+        }
+    }
+}
+
+impl Default for LatexSizes {
+    fn default() -> Self {
+        Self {
+            title: "huge".to_string(),
+            axis_label: "LARGE".to_string(),
+            ticks: "LARGE".to_string(),
+            color_bar_ticks: "Large".to_string(),
+            legend: "Large".to_string(),
+            annotation: "Large".to_string(),
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        let ramp = |ramp: [&str; 10]| ramp.map(String::from);
+        Self {
+            greens: ramp(PRIMITIVE_GREENS),
+            blues: ramp(PRIMITIVE_BLUES),
+            purples: ramp(PRIMITIVE_PURPLES),
+            greys: ramp(PRIMITIVE_GREYS),
+            black: PRIMITIVE_BLACK.to_string(),
+            white: PRIMITIVE_WHITE.to_string(),
+        }
+    }
+}
+
+impl Default for FontSizes {
+    fn default() -> Self {
+        Self {
+            x_label: 24,
+            y_label: 60,
+            ticks: 24,
+            legend: 24,
+            color_bar: 24,
+            annotation: 24,
+            contour_label: 18,
+        }
+    }
+}
+
+impl Default for LineWidths {
+    fn default() -> Self {
+        Self {
+            light: 2.0,
+            heavy: 4.0,
+            outline: 1.0,
+        }
+    }
+}
+
+impl Default for MarkerSizes {
+    fn default() -> Self {
+        Self {
+            light: 10,
+            heavy: 20,
+        }
+    }
+}
+
+impl Default for LayoutSizes {
+    fn default() -> Self {
+        Self {
+            width: 1200,
+            height: 900,
+            top_margin: 100,
+            bottom_margin: 100,
+            left_margin: 180,
+            right_margin: 100,
+            secondary_axis_margin: 80,
+            color_bar_margin: 120,
+            legend_margin: 240,
+        }
+    }
+}
+
+impl Theme {
+    /// Parses a theme from TOML.
+    pub fn from_toml(toml: &str) -> Result<Self, FigureError> {
+        let theme: Self =
+            basic_toml::from_str(toml).map_err(|error| FigureError::Theme(error.to_string()))?;
+        theme.validate()?;
+        Ok(theme)
+    }
+
+    /// Parses a theme from JSON.
+    pub fn from_json(json: &str) -> Result<Self, FigureError> {
+        let theme: Self =
+            serde_json::from_str(json).map_err(|error| FigureError::Theme(error.to_string()))?;
+        theme.validate()?;
+        Ok(theme)
+    }
+
+    /// Loads a theme from a `.toml` or `.json` file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FigureError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            Some("json") => Self::from_json(&contents),
+            _ => Err(FigureError::Theme(format!(
+                "{} is neither a .toml nor a .json file",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Resizes the figure, scaling fonts, lines, markers and margins by the square root of the
+    /// change in area so text stays legible at both small and large sizes.
+    ///
+    /// The scaling composes, so resizing twice matches resizing once up to rounding.
+    /// Nothing is scaled to or from a width or height of zero, which validation rejects.
+    pub fn sized(mut self, size: FigureSize) -> Self {
+        let (width, height) = size.dimensions();
+        let area = (width * height) as f64;
+        let current = (self.layout.width * self.layout.height) as f64;
+        let factor = match area > 0.0 && current > 0.0 {
+            true => (area / current).sqrt(),
+            false => 1.0,
+        };
+        let scale = |value: &mut usize| *value = (*value as f64 * factor).round() as usize;
+        let fonts = &mut self.fonts;
+        for font in [
+            &mut fonts.x_label,
+            &mut fonts.y_label,
+            &mut fonts.ticks,
+            &mut fonts.legend,
+            &mut fonts.color_bar,
+            &mut fonts.annotation,
+            &mut fonts.contour_label,
+        ] {
+            scale(font);
+        }
+        scale(&mut self.markers.light);
+        scale(&mut self.markers.heavy);
+        let layout = &mut self.layout;
+        for margin in [
+            &mut layout.top_margin,
+            &mut layout.bottom_margin,
+            &mut layout.left_margin,
+            &mut layout.right_margin,
+            &mut layout.secondary_axis_margin,
+            &mut layout.color_bar_margin,
+            &mut layout.legend_margin,
+        ] {
+            scale(margin);
+        }
+        let lines = &mut self.lines;
+        for line in [&mut lines.light, &mut lines.heavy, &mut lines.outline] {
+            *line *= factor;
+        }
+        self.layout.width = width;
+        self.layout.height = height;
+        self
+    }
+
+    /// Checks that the figure has an area, every LaTeX size is a sizing command
+    /// and every palette entry is a six digit hex color.
+    pub(crate) fn validate(&self) -> Result<(), FigureError> {
+        if self.layout.width == 0 || self.layout.height == 0 {
+            return Err(FigureError::Theme(
+                "the width and height of a figure must be positive".to_string(),
+            ));
+        }
+        let latex = &self.latex;
+        for size in [
+            &latex.title,
+            &latex.axis_label,
+            &latex.ticks,
+            &latex.color_bar_ticks,
+            &latex.legend,
+            &latex.annotation,
+        ] {
+            if !LATEX_SIZES.contains(&size.as_str()) {
+                return Err(FigureError::Theme(format!(
+                    "{} is not a LaTeX sizing command",
+                    size
+                )));
+            }
+        }
+        let palette = &self.palette;
+        for color in [
+            &palette.greens,
+            &palette.blues,
+            &palette.purples,
+            &palette.greys,
+        ]
+        .into_iter()
+        .flatten()
+        .chain([&palette.black, &palette.white])
+        {
+            if color.len() != 6 || !color.chars().all(|digit| digit.is_ascii_hexdigit()) {
+                return Err(FigureError::InvalidColor(color.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Palette {
+    /// The ramp behind a semantic color, or `None` for black, white and custom colors.
+    fn ramp(&self, color: &Color) -> Option<&[String; 10]> {
+        match color {
+            Color::Green => Some(&self.greens),
+            Color::Blue => Some(&self.blues),
+            Color::Purple => Some(&self.purples),
+            Color::Grey => Some(&self.greys),
+            Color::Black | Color::White | Color::Custom(_) => None,
+        }
+    }
+
+    /// Resolves a semantic color and slot to a hex value that reads well on this background.
+    /// Dark mode walks the ramps from the light end and light mode from the dark end,
+    /// so the same slot keeps its contrast in both modes. Black and white are never swapped.
+    /// Custom colors are used as given in both modes.
+    pub fn resolve(&self, mode: DisplayMode, color: &Color, color_slot: usize) -> String {
+        match (self.ramp(color), mode) {
+            (Some(ramp), DisplayMode::Dark) => ramp[color_slot].clone(),
+            (Some(ramp), DisplayMode::Light) => ramp[ramp.len() - 1 - color_slot].clone(),
+            (None, _) => match color {
+                Color::Custom(custom) => custom.hex_string(),
+                Color::White => self.white.clone(),
+                _ => self.black.clone(),
+            },
+        }
+    }
+
+    /// The color of text, axis lines and other foreground elements.
+    pub fn foreground(&self, mode: DisplayMode) -> String {
+        match mode {
+            DisplayMode::Light => self.black.clone(),
+            DisplayMode::Dark => self.white.clone(),
+        }
+    }
+
+    /// The color of an opaque background.
+    pub fn background(&self, mode: DisplayMode) -> String {
+        match mode {
+            DisplayMode::Light => self.white.clone(),
+            DisplayMode::Dark => self.black.clone(),
+        }
+    }
+}
+
+/// A theme applied in one display and text mode,
+/// which is all a trace needs to pick its colors, sizes and text.
+#[derive(Copy, Clone, Debug)]
+pub struct Skin<'a> {
+    /// The theme of the figure.
+    pub theme: &'a Theme,
+    /// Whether the figure is drawn for a light or a dark background.
+    pub mode: DisplayMode,
+    /// Whether text is rendered as LaTeX.
+    pub text: TextMode,
+    /// Whether traces sharing a legend name are grouped in the legend.
+    pub grouped_legend: bool,
+}
+
+impl Skin<'_> {
+    /// The hex value of a semantic color and slot, see [`Palette::resolve`].
+    pub fn resolve(&self, color: &Color, color_slot: usize) -> String {
+        self.theme.palette.resolve(self.mode, color, color_slot)
+    }
+
+    /// The color of text, axis lines and other foreground elements.
+    pub fn foreground(&self) -> String {
+        self.theme.palette.foreground(self.mode)
+    }
+
+    /// The color of an opaque background.
+    pub fn background(&self) -> String {
+        self.theme.palette.background(self.mode)
+    }
+
+    /// The width of a line with the given emphasis.
+    pub fn line_width(&self, emphasis: &LineEmphasis) -> f64 {
+        match emphasis {
+            LineEmphasis::Heavy => self.theme.lines.heavy,
+            _ => self.theme.lines.light,
+        }
+    }
+
+    /// Renders text in the text mode, wrapped in the given LaTeX sizing command when it is LaTeX.
+    pub fn typeset(&self, text: &str, size: &str) -> String {
+        let latex = match self.text {
+            TextMode::Latex => true,
+            TextMode::Raw => false,
+            TextMode::Auto => {
+                !text.contains('$') && ["\\", "^{", "_{"].iter().any(|mark| text.contains(mark))
+            }
+        };
+        match latex {
+            true => format!("$\\{}{{ {} }}$", size, text),
+            false => text.to_string(),
+        }
+    }
+
+    /// Whether numeric tick labels are rendered as LaTeX.
+    ///
+    /// Plain numbers never look like LaTeX, so only the LaTeX mode renders them that way.
+    pub fn latex_ticks(&self) -> bool {
+        matches!(self.text, TextMode::Latex)
+    }
+
+    /// The size of a marker with the given emphasis.
+    pub fn marker_size(&self, emphasis: &MarkerEmphasis) -> usize {
+        match emphasis {
+            MarkerEmphasis::Light => self.theme.markers.light,
+            MarkerEmphasis::Heavy => self.theme.markers.heavy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizing_to_zero_scales_nothing() {
+        let zero = Theme::default().sized(FigureSize::Custom {
+            width: 0,
+            height: 900,
+        });
+        assert_eq!(zero.fonts.legend, Theme::default().fonts.legend);
+        assert!(zero.validate().is_err());
+        let resized = zero.sized(FigureSize::Standard);
+        assert_eq!(resized.fonts.legend, Theme::default().fonts.legend);
+        assert!(resized.lines.heavy.is_finite());
+        assert!(resized.validate().is_ok());
+    }
+
+    #[test]
+    fn auto_text_is_latex_only_with_commands_or_braced_scripts() {
+        let theme = Theme::default();
+        let skin = Skin {
+            theme: &theme,
+            mode: DisplayMode::Light,
+            text: TextMode::Auto,
+            grouped_legend: false,
+        };
+        for latex in ["\\alpha", "x^{2}", "\\text{price}", "P_{t}"] {
+            assert_eq!(
+                skin.typeset(latex, "large"),
+                format!("$\\large{{ {} }}$", latex)
+            );
+        }
+        for plain in ["file_name", "x^2", "price", "$x_{1}$"] {
+            assert_eq!(skin.typeset(plain, "large"), plain);
+        }
+    }
+
+    #[test]
+    fn light_mode_walks_the_ramps_from_the_dark_end() {
+        let palette = Palette::default();
+        for (color, ramp) in [
+            (Color::Green, &palette.greens),
+            (Color::Blue, &palette.blues),
+            (Color::Purple, &palette.purples),
+            (Color::Grey, &palette.greys),
+        ] {
+            for slot in 0..10 {
+                assert_eq!(palette.resolve(DisplayMode::Dark, &color, slot), ramp[slot]);
+                assert_eq!(
+                    palette.resolve(DisplayMode::Light, &color, slot),
+                    ramp[9 - slot]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn black_white_and_custom_colors_are_never_swapped() {
+        let palette = Palette::default();
+        let custom = Color::Custom(CustomColor::rgb(0x12, 0x34, 0x56));
+        for mode in [DisplayMode::Light, DisplayMode::Dark] {
+            assert_eq!(palette.resolve(mode, &Color::Black, 3), palette.black);
+            assert_eq!(palette.resolve(mode, &Color::White, 3), palette.white);
+            assert_eq!(palette.resolve(mode, &custom, 3), "123456");
+        }
+        assert_ne!(palette.black, palette.white);
+    }
+
+    #[test]
+    fn partial_themes_keep_the_defaults() {
+        let toml = "[fonts]\nlegend = 30\n\n[palette]\nblack = \"111111\"\n";
+        let json = r#"{"fonts": {"legend": 30}, "palette": {"black": "111111"}}"#;
+        let default = Theme::default();
+        for theme in [Theme::from_toml(toml), Theme::from_json(json)] {
+            let theme = theme.unwrap();
+            assert_eq!(theme.fonts.legend, 30);
+            assert_eq!(theme.fonts.ticks, default.fonts.ticks);
+            assert_eq!(theme.palette.black, "111111");
+            assert_eq!(theme.palette.greens, default.palette.greens);
+            assert_eq!(theme.layout.width, default.layout.width);
+            assert_eq!(theme.latex.title, default.latex.title);
+        }
+    }
+
+    #[test]
+    fn invalid_palette_entries_are_rejected() {
+        let error = Theme::from_toml("[palette]\nwhite = \"#ffffff\"\n").unwrap_err();
+        assert!(matches!(error, FigureError::InvalidColor(color) if color == "#ffffff"));
+        let error = Theme::from_json(r#"{"palette": {"black": "abc"}}"#).unwrap_err();
+        assert!(matches!(error, FigureError::InvalidColor(color) if color == "abc"));
+        assert!(matches!(Theme::from_json("{"), Err(FigureError::Theme(_))));
+    }
+
+    #[test]
+    fn load_picks_the_format_by_extension() {
+        let directory = std::env::temp_dir().join(format!("theme-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let toml = directory.join("theme.toml");
+        fs::write(&toml, "[layout]\nwidth = 800\n").unwrap();
+        assert_eq!(Theme::load(&toml).unwrap().layout.width, 800);
+        let yaml = directory.join("theme.yaml");
+        fs::write(&yaml, "layout:\n  width: 800\n").unwrap();
+        assert!(matches!(Theme::load(&yaml), Err(FigureError::Theme(_))));
+        assert!(matches!(
+            Theme::load(directory.join("missing.toml")),
+            Err(FigureError::Io(_))
+        ));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}